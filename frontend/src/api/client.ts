@@ -1,6 +1,7 @@
 import type {
     AppliedInternship,
     Notification,
+    OfferFacets,
     OffersResponse,
     RecommendedOffersResponse,
 } from "../types";
@@ -21,9 +22,10 @@ async function readErrorMessage(res: Response, fallback: string): Promise<string
     }
 }
 
-export async function fetchOffers(city?: string, domain?: string, limit: number = 10): Promise<OffersResponse> {
+export async function fetchOffers(city?: string, domain?: string, limit: number = 10, q?: string): Promise<OffersResponse> {
     const params = new URLSearchParams();
     params.append("limit", limit.toString());
+    if (q) params.append("q", q);
     if (city) params.append("city", city);
     if (domain) params.append("domain", domain);
 
@@ -34,6 +36,18 @@ export async function fetchOffers(city?: string, domain?: string, limit: number
     return res.json();
 }
 
+export async function fetchOfferFacets(city?: string, domain?: string): Promise<OfferFacets> {
+    const params = new URLSearchParams();
+    if (city) params.append("city", city);
+    if (domain) params.append("domain", domain);
+
+    const res = await fetch(`${API_BASE_URL}/offers/facets?${params.toString()}`);
+    if (!res.ok) {
+        throw new Error(await readErrorMessage(res, "Failed to fetch offer facets"));
+    }
+    return res.json();
+}
+
 export async function fetchRecommendedOffers(studentId: string, limit: number = 5, sortBy?: string): Promise<RecommendedOffersResponse> {
     const params = new URLSearchParams();
     params.append("limit", limit.toString());
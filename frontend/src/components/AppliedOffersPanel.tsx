@@ -1,6 +1,7 @@
 import { useQuery } from "@tanstack/react-query";
 import { fetchAppliedInternships } from "../api/client";
 import type { AppliedInternship } from "../types";
+import { formatSalary } from "@/lib/offer-metrics";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
@@ -52,7 +53,7 @@ function ApplicationRow({ internship }: { internship: AppliedInternship }) {
                     </p>
                     <p className="text-sm text-muted-foreground">
                         {internship.offer
-                            ? `${internship.offer.city} · ${Math.round(internship.offer.salary).toLocaleString("en-GB")} EUR${period ? ` · ${period}` : ""}`
+                            ? `${internship.offer.city} · ${formatSalary(internship.offer.salary, internship.offer.currency, internship.offer.salary_period)}${period ? ` · ${period}` : ""}`
                             : `Offer ID: ${internship.offer_id}`}
                     </p>
                 </div>
@@ -3,12 +3,19 @@ import { Button } from "@/components/ui/button";
 import { Card, CardContent } from "@/components/ui/card";
 import { Input } from "@/components/ui/input";
 import { Search } from "lucide-react";
+import type { FacetCount } from "../types";
 
-const quickDomains = ["AI", "IT", "Data", "Finance"];
+const fallbackDomains = ["AI", "IT", "Data", "Finance"];
+const QUICK_DOMAIN_COUNT = 4;
 
 interface FiltersBarProps {
+    keywords: string;
     city: string;
     domain: string;
+    /** Known cities and domains with their number of offers, most common first. */
+    cities?: FacetCount[];
+    domains?: FacetCount[];
+    onKeywordsChange: (value: string) => void;
     onCityChange: (value: string) => void;
     onDomainChange: (value: string) => void;
     onSubmit: () => void;
@@ -17,38 +24,69 @@ interface FiltersBarProps {
 }
 
 export function FiltersBar({
+    keywords,
     city,
     domain,
+    cities = [],
+    domains = [],
+    onKeywordsChange,
     onCityChange,
     onDomainChange,
     onSubmit,
     onQuickDomain,
     onClear,
 }: FiltersBarProps) {
-    const hasFilters = city.trim().length > 0 || domain.trim().length > 0;
+    const hasFilters =
+        keywords.trim().length > 0 || city.trim().length > 0 || domain.trim().length > 0;
+    const quickDomains: FacetCount[] =
+        domains.length > 0
+            ? domains.slice(0, QUICK_DOMAIN_COUNT)
+            : fallbackDomains.map((value) => ({ value, count: 0 }));
 
     return (
         <Card className="border-border/70 bg-card/95 shadow-sm">
             <CardContent className="space-y-4 px-5 py-5">
                 <form
-                    className="grid gap-3 lg:grid-cols-[1fr_1fr_auto]"
+                    className="grid gap-3 lg:grid-cols-[2fr_1fr_1fr_auto]"
                     onSubmit={(event) => {
                         event.preventDefault();
                         onSubmit();
                     }}
                 >
+                    <Input
+                        value={keywords}
+                        onChange={(event) => onKeywordsChange(event.target.value)}
+                        placeholder="Keywords"
+                        className="h-11"
+                    />
                     <Input
                         value={city}
                         onChange={(event) => onCityChange(event.target.value)}
                         placeholder="City"
                         className="h-11"
+                        list="filter-cities"
                     />
+                    <datalist id="filter-cities">
+                        {cities.map(({ value, count }) => (
+                            <option key={value} value={value}>
+                                {count} offers
+                            </option>
+                        ))}
+                    </datalist>
                     <Input
                         value={domain}
                         onChange={(event) => onDomainChange(event.target.value)}
                         placeholder="Domain"
                         className="h-11"
+                        list="filter-domains"
                     />
+                    <datalist id="filter-domains">
+                        {domains.map(({ value, count }) => (
+                            <option key={value} value={value}>
+                                {count} offers
+                            </option>
+                        ))}
+                    </datalist>
                     <Button type="submit" className="h-11 px-5">
                         <Search className="h-4 w-4" />
                         Search
@@ -56,7 +94,7 @@ export function FiltersBar({
                 </form>
 
                 <div className="flex flex-wrap items-center gap-2">
-                    {quickDomains.map((value) => {
+                    {quickDomains.map(({ value, count }) => {
                         const isActive = domain.trim().toLowerCase() === value.toLowerCase();
 
                         return (
@@ -68,6 +106,9 @@ export function FiltersBar({
                                 onClick={() => onQuickDomain(value)}
                             >
                                 {value}
+                                {count > 0 ? (
+                                    <span className="text-xs opacity-70">{count}</span>
+                                ) : null}
                             </Button>
                         );
                     })}
@@ -78,7 +119,7 @@ export function FiltersBar({
                         </Button>
                     ) : (
                         <Badge variant="secondary" className="rounded-full px-3 py-1">
-                            Start with a city or domain
+                            Start with keywords, a city or a domain
                         </Badge>
                     )}
                 </div>
@@ -5,6 +5,7 @@ import { applyInternship } from "../api/client";
 import { MetricsBlock } from "./MetricsBlock";
 import {
     formatOfferPeriod,
+    formatSalary,
     getLeadingMetric,
     getOfferMatchScore,
 } from "@/lib/offer-metrics";
@@ -13,7 +14,7 @@ import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import { Card, CardContent, CardFooter, CardHeader, CardTitle } from "@/components/ui/card";
 import { Separator } from "@/components/ui/separator";
-import { CheckCircle2, Loader2, MapPin, XCircle } from "lucide-react";
+import { Building2, CheckCircle2, Loader2, MapPin, XCircle } from "lucide-react";
 
 interface OfferCardProps {
     offer: EnrichedOffer;
@@ -61,21 +62,49 @@ export function OfferCard({ offer, studentId }: OfferCardProps) {
                                 <Badge variant="secondary" className="rounded-full px-2.5 py-0.5">
                                     {offer.domain}
                                 </Badge>
+                                {offer.skills.map((skill) => (
+                                    <Badge
+                                        key={skill}
+                                        variant={offer.skill_match?.matched.includes(skill) ? "default" : "outline"}
+                                        className="rounded-full px-2.5 py-0.5"
+                                    >
+                                        {skill}
+                                    </Badge>
+                                ))}
                             </div>
                             <div className="space-y-2">
                                 <CardTitle className="text-xl leading-tight tracking-tight text-balance">
                                     {offer.title}
                                 </CardTitle>
                                 <div className="flex flex-wrap items-center gap-x-4 gap-y-2 text-sm text-muted-foreground">
+                                    {offer.company && (
+                                        <span className="inline-flex items-center gap-1.5">
+                                            <Building2 className="h-4 w-4" />
+                                            {offer.company.name}
+                                        </span>
+                                    )}
                                     <span className="inline-flex items-center gap-1.5">
                                         <MapPin className="h-4 w-4" />
                                         {offer.city}
                                     </span>
                                     <span className="font-medium text-foreground">
-                                        {Math.round(offer.salary).toLocaleString("en-GB")} EUR
+                                        {formatSalary(offer.salary_monthly_eur)}
                                     </span>
+                                    {(offer.currency !== "EUR" || offer.salary_period !== "monthly") && (
+                                        <span>({formatSalary(offer.salary, offer.currency, offer.salary_period)})</span>
+                                    )}
                                     <span>{formatOfferPeriod(offer.startDate, offer.endDate)}</span>
+                                    {offer.positions > 1 && (
+                                        <span>
+                                            {offer.remaining} of {offer.positions} seats left
+                                        </span>
+                                    )}
                                 </div>
+                                {offer.description && (
+                                    <p className="line-clamp-2 text-sm text-muted-foreground">
+                                        {offer.description}
+                                    </p>
+                                )}
                             </div>
                         </div>
 
@@ -1,4 +1,4 @@
-import type { EnrichedOffer, EnrichedScores } from "../types";
+import type { EnrichedOffer, EnrichedScores, SalaryPeriod } from "../types";
 
 export const offerMetrics = [
     {
@@ -82,8 +82,13 @@ export function sortOffers(offers: EnrichedOffer[], sortBy: string): EnrichedOff
     const next = [...offers];
 
     switch (sortBy) {
+        // Keeps the order of a keyword search, most relevant first.
+        case "relevance":
+            return next;
         case "salary":
-            return next.sort((left, right) => right.salary - left.salary);
+            return next.sort(
+                (left, right) => right.salary_monthly_eur - left.salary_monthly_eur,
+            );
         case "safety":
             return next.sort(
                 (left, right) =>
@@ -140,3 +145,18 @@ export function formatOfferPeriod(startDate: string, endDate: string): string {
         year: "numeric",
     })}`;
 }
+
+const periodSuffix: Record<SalaryPeriod, string> = {
+    hourly: "/h",
+    monthly: "/month",
+    yearly: "/year",
+};
+
+export function formatSalary(
+    salary: number,
+    currency: string = "EUR",
+    period: SalaryPeriod = "monthly",
+): string {
+    const amount = period === "hourly" ? salary.toFixed(2) : Math.round(salary).toLocaleString("en-GB");
+    return `${amount} ${currency}${periodSuffix[period]}`;
+}
@@ -1,6 +1,6 @@
 import { useState } from "react";
 import { useQuery } from "@tanstack/react-query";
-import { fetchOffers } from "../api/client";
+import { fetchOfferFacets, fetchOffers } from "../api/client";
 import { FiltersBar } from "../components/FiltersBar";
 import { OfferCard } from "../components/OfferCard";
 import { Button } from "@/components/ui/button";
@@ -19,25 +19,42 @@ import { AlertCircle, PackageSearch } from "lucide-react";
 const PAGE_SIZE = 6;
 
 export function OffersExplorer() {
+    const [draftKeywords, setDraftKeywords] = useState("");
     const [draftCity, setDraftCity] = useState("");
     const [draftDomain, setDraftDomain] = useState("");
+    const [appliedKeywords, setAppliedKeywords] = useState("");
     const [appliedCity, setAppliedCity] = useState("");
     const [appliedDomain, setAppliedDomain] = useState("");
     const [sortBy, setSortBy] = useState("best_match");
     const [page, setPage] = useState(1);
 
     const { data, isLoading, isError, error } = useQuery({
-        queryKey: ["offers", appliedCity, appliedDomain],
+        queryKey: ["offers", appliedKeywords, appliedCity, appliedDomain],
         queryFn: () =>
             fetchOffers(
                 appliedCity || undefined,
                 appliedDomain || undefined,
                 100,
+                appliedKeywords || undefined,
             ),
-        enabled: !!(appliedCity || appliedDomain),
     });
 
+    // Unfiltered, so that every city and domain stays suggested.
+    const { data: facets } = useQuery({
+        queryKey: ["offer-facets"],
+        queryFn: () => fetchOfferFacets(),
+    });
+
+    const applyKeywords = (value: string) => {
+        setAppliedKeywords(value);
+        // Offers come most relevant first, which other sorts would override.
+        setSortBy((current) =>
+            value ? "relevance" : current === "relevance" ? "best_match" : current,
+        );
+    };
+
     const handleSearch = () => {
+        applyKeywords(draftKeywords.trim());
         setAppliedCity(draftCity.trim());
         setAppliedDomain(draftDomain.trim());
         setPage(1);
@@ -45,12 +62,15 @@ export function OffersExplorer() {
 
     const handleQuickDomain = (value: string) => {
         setDraftDomain(value);
+        applyKeywords(draftKeywords.trim());
         setAppliedCity(draftCity.trim());
         setAppliedDomain(value);
         setPage(1);
     };
 
     const handleClear = () => {
+        setDraftKeywords("");
+        applyKeywords("");
         setDraftCity("");
         setDraftDomain("");
         setAppliedCity("");
@@ -77,13 +97,17 @@ export function OffersExplorer() {
                 <div className="space-y-2">
                     <h1 className="text-3xl font-semibold tracking-tight">Explore opportunities</h1>
                     <p className="max-w-2xl text-sm text-muted-foreground">
-                        Filter by city or domain to surface the strongest internships quickly.
+                        Browse the whole catalogue, or search by keywords, city or domain to surface the strongest internships quickly.
                     </p>
                 </div>
 
                 <FiltersBar
+                    keywords={draftKeywords}
                     city={draftCity}
                     domain={draftDomain}
+                    cities={facets?.cities}
+                    domains={facets?.domains}
+                    onKeywordsChange={setDraftKeywords}
                     onCityChange={setDraftCity}
                     onDomainChange={setDraftDomain}
                     onSubmit={handleSearch}
@@ -91,7 +115,7 @@ export function OffersExplorer() {
                     onClear={handleClear}
                 />
 
-                {(appliedCity || appliedDomain) && !isError && (
+                {!isError && (
                     <div className="flex flex-col gap-4 rounded-xl border bg-card/70 px-5 py-4 md:flex-row md:items-center md:justify-between">
                         <div className="space-y-1">
                             <div className="flex items-center gap-2">
@@ -99,9 +123,9 @@ export function OffersExplorer() {
                                 {!isLoading && <Badge variant="secondary">{count}</Badge>}
                             </div>
                             <p className="text-sm text-muted-foreground">
-                                {appliedCity || appliedDomain
-                                    ? `Showing results${appliedCity ? ` in ${appliedCity}` : ""}${appliedDomain ? ` for ${appliedDomain}` : ""}.`
-                                    : "Add a city or domain to browse offers."}
+                                {appliedKeywords || appliedCity || appliedDomain
+                                    ? `Showing results${appliedKeywords ? ` matching "${appliedKeywords}"` : ""}${appliedCity ? ` in ${appliedCity}` : ""}${appliedDomain ? ` for ${appliedDomain}` : ""}.`
+                                    : "Browsing the full catalogue, newest first."}
                             </p>
                         </div>
 
@@ -112,6 +136,9 @@ export function OffersExplorer() {
                                     <SelectValue placeholder="Best match" />
                                 </SelectTrigger>
                                 <SelectContent>
+                                    {appliedKeywords && (
+                                        <SelectItem value="relevance">Relevance</SelectItem>
+                                    )}
                                     <SelectItem value="best_match">Best match</SelectItem>
                                     <SelectItem value="salary">Salary</SelectItem>
                                     <SelectItem value="safety">Safety</SelectItem>
@@ -122,18 +149,6 @@ export function OffersExplorer() {
                     </div>
                 )}
 
-                {!appliedCity && !appliedDomain ? (
-                    <Card className="border-dashed bg-muted/20">
-                        <CardContent className="flex flex-col items-center px-6 py-16 text-center">
-                            <PackageSearch className="mb-4 h-10 w-10 text-muted-foreground/60" />
-                            <h3 className="text-lg font-semibold">Start with a filter</h3>
-                            <p className="mt-2 max-w-md text-sm text-muted-foreground">
-                                The current gateway requires at least one filter. Enter a city or a domain to load offers.
-                            </p>
-                        </CardContent>
-                    </Card>
-                ) : null}
-
                 {isLoading ? (
                     <div className="grid grid-cols-1 gap-5 md:grid-cols-2 xl:grid-cols-3">
                         {Array.from({ length: 6 }).map((_, index) => (
@@ -171,7 +186,7 @@ export function OffersExplorer() {
                     </div>
                 ) : null}
 
-                {!isLoading && !isError && (appliedCity || appliedDomain) ? (
+                {!isLoading && !isError ? (
                     count === 0 ? (
                         <Card className="border-dashed bg-muted/20">
                             <CardContent className="flex flex-col items-center px-6 py-16 text-center">
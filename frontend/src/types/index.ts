@@ -12,15 +12,39 @@ export interface EnrichedNews {
     tags: string[];
 }
 
+export interface CompanySummary {
+    id: string;
+    name: string;
+    website: string | null;
+}
+
+export interface SkillOverlap {
+    score: number;
+    matched: string[];
+    missing: string[];
+}
+
+export type SalaryPeriod = "hourly" | "monthly" | "yearly";
+
 export interface EnrichedOffer {
     id: string;
     title: string;
     link: string;
     city: string;
     domain: string;
+    description?: string;
+    skills: string[];
     salary: number;
+    currency: string;
+    salary_period: SalaryPeriod;
+    salary_monthly_eur: number;
     startDate: string;
     endDate: string;
+    positions: number;
+    remaining: number;
+    company: CompanySummary | null;
+    distance_km?: number;
+    skill_match?: SkillOverlap;
     scores: EnrichedScores;
     latest_news: EnrichedNews[];
 }
@@ -30,6 +54,7 @@ export interface Student {
     firstname: string;
     name: string;
     domain: string;
+    skills: string[];
 }
 
 export interface Notification {
@@ -48,6 +73,9 @@ export interface AppliedOffer {
     city: string;
     domain: string;
     salary: number;
+    currency?: string;
+    salary_period?: SalaryPeriod;
+    salary_monthly_eur?: number;
     start_date: string;
     end_date: string;
 }
@@ -62,6 +90,29 @@ export interface AppliedInternship {
 
 export interface OffersResponse {
     offers: EnrichedOffer[];
+    total: number;
+    offset: number;
+    limit: number;
+    next_offset: number | null;
+}
+
+export interface FacetCount {
+    value: string;
+    count: number;
+}
+
+export interface SalaryBucket {
+    min: number;
+    max?: number;
+    count: number;
+}
+
+export interface OfferFacets {
+    total: number;
+    cities: FacetCount[];
+    domains: FacetCount[];
+    salaries: SalaryBucket[];
+    start_months: FacetCount[];
 }
 
 export interface RecommendedOffersResponse {
@@ -24,24 +24,32 @@ impl AmqpPublisher {
                 FieldTable::default(),
             )
             .await?;
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await?;
         Ok(Self {
             channel: Arc::new(Mutex::new(channel)),
         })
     }
 
-    pub async fn publish(&self, routing_key: &str, payload: &[u8]) -> Result<(), lapin::Error> {
+    /// Publishes and waits for the broker to confirm the message.
+    pub async fn publish(&self, routing_key: &str, payload: &[u8]) -> Result<(), anyhow::Error> {
         let ch = self.channel.lock().await;
-        ch.basic_publish(
-            "polymove.events",
-            routing_key,
-            BasicPublishOptions::default(),
-            payload,
-            BasicProperties::default()
-                .with_content_type("application/json".into())
-                .with_delivery_mode(2),
-        )
-        .await?
-        .await?;
+        let confirmation = ch
+            .basic_publish(
+                "polymove.events",
+                routing_key,
+                BasicPublishOptions::default(),
+                payload,
+                BasicProperties::default()
+                    .with_content_type("application/json".into())
+                    .with_delivery_mode(2),
+            )
+            .await?
+            .await?;
+        if confirmation.is_nack() {
+            return Err(anyhow::anyhow!("RabbitMQ rejected {}", routing_key));
+        }
         Ok(())
     }
 }
@@ -0,0 +1,137 @@
+use super::{AppResult, AppState, ErrorResponse};
+use crate::application::company_service::{CreateCompanyParams, UpdateCompanyParams};
+use crate::domain::company::Company;
+use crate::domain::ports::company_repository::{CompanyError, CompanyRepository};
+use crate::domain::ports::offer_repository::OfferRepository;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub struct CreateCompanyRequest {
+    pub name: String,
+    pub website: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub cities: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateCompanyRequest {
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub description: Option<String>,
+    pub cities: Option<Vec<String>>,
+}
+
+impl From<CompanyError> for Response {
+    fn from(err: CompanyError) -> Self {
+        let (status, message, fields) = match err {
+            CompanyError::NotFound => (
+                StatusCode::NOT_FOUND,
+                "Company not found".to_string(),
+                Vec::new(),
+            ),
+            CompanyError::ValidationFailed(fields) => (
+                StatusCode::BAD_REQUEST,
+                "Invalid company".to_string(),
+                fields,
+            ),
+            err @ CompanyError::InUse(_) => (StatusCode::CONFLICT, err.to_string(), Vec::new()),
+            CompanyError::DatabaseError(msg) => {
+                tracing::error!("Database error: {}", msg);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error".to_string(),
+                    Vec::new(),
+                )
+            }
+        };
+        (
+            status,
+            Json(ErrorResponse {
+                error: message,
+                fields,
+            }),
+        )
+            .into_response()
+    }
+}
+
+pub async fn create_company<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Json(payload): Json<CreateCompanyRequest>,
+) -> AppResult<(StatusCode, Json<Company>)> {
+    let company = state
+        .companies
+        .create_company(CreateCompanyParams {
+            name: payload.name,
+            website: payload.website,
+            description: payload.description,
+            cities: payload.cities,
+        })
+        .await
+        .map_err(Response::from)?;
+    Ok((StatusCode::CREATED, Json(company)))
+}
+
+pub async fn list_companies<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+) -> AppResult<Json<Vec<Company>>> {
+    let companies = state
+        .companies
+        .list_companies()
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(companies))
+}
+
+pub async fn get_company<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Company>> {
+    let company = state
+        .companies
+        .get_company(&id)
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(company))
+}
+
+pub async fn update_company<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateCompanyRequest>,
+) -> AppResult<Json<Company>> {
+    let company = state
+        .companies
+        .update_company(
+            &id,
+            UpdateCompanyParams {
+                name: payload.name,
+                website: payload.website,
+                description: payload.description,
+                cities: payload.cities,
+            },
+        )
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(company))
+}
+
+pub async fn delete_company<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Path(id): Path<String>,
+) -> AppResult<StatusCode> {
+    state
+        .companies
+        .delete_company(&id)
+        .await
+        .map_err(Response::from)?;
+    Ok(StatusCode::OK)
+}
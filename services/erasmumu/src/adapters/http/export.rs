@@ -0,0 +1,153 @@
+use super::OfferFilterParams;
+use crate::domain::offer::Offer;
+use crate::domain::ports::offer_repository::OfferError;
+use axum::body::Bytes;
+use chrono::NaiveDate;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "offers.csv",
+            ExportFormat::Ndjson => "offers.ndjson",
+        }
+    }
+}
+
+/// Takes the same filters as `GET /offer`.
+#[derive(Deserialize)]
+pub struct ExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(flatten)]
+    pub filters: OfferFilterParams,
+}
+
+/// Flat CSV columns, in the same order as [`CSV_HEADER`].
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    id: &'a str,
+    title: &'a str,
+    link: &'a str,
+    city: &'a str,
+    domain: &'a str,
+    description: Option<&'a str>,
+    /// `;`-separated, as accepted by CSV imports.
+    skills: String,
+    company_id: Option<&'a str>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    salary: f64,
+    currency: &'a str,
+    salary_period: &'a str,
+    salary_monthly_eur: f64,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    status: &'a str,
+    positions: u32,
+    remaining: u32,
+}
+
+const CSV_HEADER: [&str; 19] = [
+    "id",
+    "title",
+    "link",
+    "city",
+    "domain",
+    "description",
+    "skills",
+    "company_id",
+    "latitude",
+    "longitude",
+    "salary",
+    "currency",
+    "salary_period",
+    "salary_monthly_eur",
+    "start_date",
+    "end_date",
+    "status",
+    "positions",
+    "remaining",
+];
+
+impl<'a> From<&'a Offer> for CsvRow<'a> {
+    fn from(offer: &'a Offer) -> Self {
+        Self {
+            id: &offer.id,
+            title: &offer.title,
+            link: &offer.link,
+            city: &offer.city,
+            domain: &offer.domain,
+            description: offer.description.as_deref(),
+            skills: offer.skills.join(";"),
+            company_id: offer.company_id.as_deref(),
+            latitude: offer.location.map(|point| point.lat),
+            longitude: offer.location.map(|point| point.lon),
+            salary: offer.salary,
+            currency: &offer.currency,
+            salary_period: offer.salary_period.as_str(),
+            salary_monthly_eur: offer.salary_monthly_eur,
+            start_date: offer.start_date,
+            end_date: offer.end_date,
+            status: offer.status.as_str(),
+            positions: offer.positions,
+            remaining: offer.remaining,
+        }
+    }
+}
+
+fn csv_line<F>(write: F) -> Result<Bytes, OfferError>
+where
+    F: FnOnce(&mut csv::Writer<Vec<u8>>) -> csv::Result<()>,
+{
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    write(&mut writer).map_err(|e| OfferError::InvalidData(e.to_string()))?;
+    writer
+        .into_inner()
+        .map(Bytes::from)
+        .map_err(|e| OfferError::InvalidData(e.to_string()))
+}
+
+/// Encodes offers one line at a time as they come out of the cursor.
+pub fn encode(
+    format: ExportFormat,
+    offers: BoxStream<'static, Result<Offer, OfferError>>,
+) -> BoxStream<'static, Result<Bytes, OfferError>> {
+    match format {
+        ExportFormat::Csv => {
+            let header = csv_line(|writer| writer.write_record(CSV_HEADER));
+            stream::once(async move { header })
+                .chain(offers.and_then(|offer| async move {
+                    csv_line(|writer| writer.serialize(CsvRow::from(&offer)))
+                }))
+                .boxed()
+        }
+        ExportFormat::Ndjson => offers
+            .and_then(|offer| async move {
+                let mut line = serde_json::to_vec(&offer)
+                    .map_err(|e| OfferError::InvalidData(e.to_string()))?;
+                line.push(b'\n');
+                Ok(Bytes::from(line))
+            })
+            .boxed(),
+    }
+}
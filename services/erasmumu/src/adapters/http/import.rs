@@ -0,0 +1,162 @@
+use super::CreateOfferRequest;
+use crate::application::offer_service::ImportRow;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ImportFormat {
+    Json,
+    Ndjson,
+    Csv,
+}
+
+impl ImportFormat {
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime.to_ascii_lowercase().as_str() {
+            "application/json" => Some(ImportFormat::Json),
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" => {
+                Some(ImportFormat::Ndjson)
+            }
+            "text/csv" => Some(ImportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Splits the body into rows. Only a body that is not a list of rows at all is an
+/// error; a row that fails to parse is kept with its error so it can be reported.
+pub fn parse_rows(format: ImportFormat, body: &str) -> Result<Vec<ImportRow>, String> {
+    match format {
+        ImportFormat::Json => {
+            let values: Vec<serde_json::Value> = serde_json::from_str(body)
+                .map_err(|e| format!("Expected a JSON array of offers: {}", e))?;
+            Ok(values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| ImportRow {
+                    row: index + 1,
+                    params: serde_json::from_value::<CreateOfferRequest>(value)
+                        .map(Into::into)
+                        .map_err(|e| e.to_string()),
+                })
+                .collect())
+        }
+        ImportFormat::Ndjson => Ok(body
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| ImportRow {
+                row: index + 1,
+                params: serde_json::from_str::<CreateOfferRequest>(line)
+                    .map(Into::into)
+                    .map_err(|e| e.to_string()),
+            })
+            .collect()),
+        ImportFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(body.as_bytes());
+            Ok(reader
+                .deserialize::<CreateOfferRequest>()
+                .enumerate()
+                .map(|(index, record)| ImportRow {
+                    row: index + 1,
+                    params: record.map(Into::into).map_err(|e| e.to_string()),
+                })
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFER: &str = r#"{"title": "Data intern", "link": "https://example.com/data", "city": "Lyon", "domain": "AI", "salary": 1200, "start_date": "2026-06-01", "end_date": "2026-09-30", "skills": ["Rust"]}"#;
+
+    /// The title of each parsed row, or its error, by row number.
+    fn outcomes(rows: Vec<ImportRow>) -> Vec<(usize, Result<String, String>)> {
+        rows.into_iter()
+            .map(|row| (row.row, row.params.map(|params| params.title)))
+            .collect()
+    }
+
+    #[test]
+    fn content_types_pick_the_format() {
+        assert!(matches!(
+            ImportFormat::from_content_type("Application/JSON; charset=utf-8"),
+            Some(ImportFormat::Json)
+        ));
+        assert!(matches!(
+            ImportFormat::from_content_type("application/jsonl"),
+            Some(ImportFormat::Ndjson)
+        ));
+        assert!(matches!(
+            ImportFormat::from_content_type("text/csv"),
+            Some(ImportFormat::Csv)
+        ));
+        assert!(ImportFormat::from_content_type("text/plain").is_none());
+    }
+
+    #[test]
+    fn json_rows_are_numbered_by_position() {
+        let body = format!(r#"[{OFFER}, {{"title": "No link"}}]"#);
+
+        let rows = outcomes(parse_rows(ImportFormat::Json, &body).unwrap());
+
+        assert_eq!(rows[0], (1, Ok("Data intern".to_string())));
+        assert_eq!(rows[1].0, 2);
+        assert!(
+            rows[1]
+                .1
+                .as_ref()
+                .unwrap_err()
+                .contains("missing field `link`")
+        );
+    }
+
+    #[test]
+    fn json_bodies_must_be_arrays() {
+        let error = parse_rows(ImportFormat::Json, OFFER).err().unwrap();
+        assert!(error.starts_with("Expected a JSON array of offers"));
+    }
+
+    #[test]
+    fn ndjson_rows_keep_their_line_numbers_across_blank_lines() {
+        let body = format!("{OFFER}\n\n  \n{{not json\n{OFFER}\n");
+
+        let rows = outcomes(parse_rows(ImportFormat::Ndjson, &body).unwrap());
+
+        let numbers: Vec<usize> = rows.iter().map(|(row, _)| *row).collect();
+        assert_eq!(numbers, [1, 4, 5]);
+        assert!(rows[0].1.is_ok());
+        assert!(
+            rows[1]
+                .1
+                .as_ref()
+                .unwrap_err()
+                .contains("key must be a string")
+        );
+        assert!(rows[2].1.is_ok());
+    }
+
+    #[test]
+    fn csv_columns_are_mapped_by_header() {
+        let body = "city , title,link,domain,salary,start_date,end_date,skills,currency\n\
+            Lyon, Data intern ,https://example.com/data,AI,1200,2026-06-01,2026-09-30, Rust ; SQL ,CHF\n\
+            Paris,Bad salary,https://example.com/bad,AI,lots,2026-06-01,2026-09-30,,\n";
+
+        let rows = parse_rows(ImportFormat::Csv, body).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].row, 1);
+        let params = rows[0].params.as_ref().unwrap();
+        assert_eq!(params.title, "Data intern");
+        assert_eq!(params.city, "Lyon");
+        assert_eq!(params.skills, ["Rust", "SQL"]);
+        assert_eq!(params.currency.as_deref(), Some("CHF"));
+        assert_eq!(rows[1].row, 2);
+        let error = rows[1].params.as_ref().err().unwrap();
+        assert!(error.contains("line: 3"), "{error}");
+        assert!(error.contains("invalid float literal"), "{error}");
+    }
+}
@@ -1,26 +1,139 @@
-use crate::adapters::amqp::publisher::AmqpPublisher;
-use crate::application::offer_service::{CreateOfferParams, OfferService, UpdateOfferParams};
-use crate::domain::offer::Offer;
-use crate::domain::ports::offer_repository::{OfferError, OfferRepository};
+mod companies;
+mod export;
+mod import;
+pub mod webhooks;
+
+use crate::application::company_service::CompanyService;
+use crate::application::offer_service::{
+    CreateOfferParams, ImportReport, OfferService, UpdateOfferParams,
+};
+use crate::domain::facets::OfferFacets;
+use crate::domain::geo::GeoNear;
+use crate::domain::history::HistoryEntry;
+use crate::domain::offer::{
+    Offer, OfferPage, OfferQuery, OfferSort, OfferStatus, PageRequest, Reservation, SkillMatch,
+    SortOrder,
+};
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::{FieldError, OfferError, OfferRepository};
+use crate::domain::salary::SalaryPeriod;
 use axum::{
     Json, Router,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    body::Body,
+    extract::{DefaultBodyLimit, FromRequestParts, Path, Query, State},
+    http::{HeaderMap, StatusCode, header, request::Parts},
     response::{IntoResponse, Response},
     routing::{get, post},
 };
+use chrono::{DateTime, NaiveDate, Utc};
+use export::ExportParams;
+use futures_util::TryStreamExt;
+use import::ImportFormat;
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::sync::Arc;
 
-#[derive(Deserialize)]
+const IMPORT_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize)]
 pub struct CreateOfferRequest {
     pub title: String,
     pub link: String,
     pub city: String,
     pub domain: String,
+    pub description: Option<String>,
     pub salary: f64,
+    pub currency: Option<String>,
+    pub salary_period: Option<SalaryPeriod>,
     pub start_date: String,
     pub end_date: String,
+    pub status: Option<OfferStatus>,
+    pub positions: Option<u32>,
+    pub company_id: Option<String>,
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "skill_list")]
+    pub skills: Vec<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Accepts skills as a list or, for CSV imports, as one `;`-separated field.
+fn skill_list<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    struct SkillList;
+
+    impl<'de> serde::de::Visitor<'de> for SkillList {
+        type Value = Vec<String>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a list of skills or a ;-separated string")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            Ok(split_skills(value, ';'))
+        }
+
+        // CSV cells are typed by their look, so a lone skill may not be a string.
+        fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<Self::Value, E> {
+            self.visit_str(&value.to_string())
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            self.visit_str(&value.to_string())
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            self.visit_str(&value.to_string())
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Self::Value, E> {
+            self.visit_str(&value.to_string())
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut skills = Vec::new();
+            while let Some(skill) = seq.next_element()? {
+                skills.push(skill);
+            }
+            Ok(skills)
+        }
+    }
+
+    deserializer.deserialize_any(SkillList)
+}
+
+fn split_skills(raw: &str, separator: char) -> Vec<String> {
+    raw.split(separator)
+        .map(str::trim)
+        .filter(|skill| !skill.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl From<CreateOfferRequest> for CreateOfferParams {
+    fn from(payload: CreateOfferRequest) -> Self {
+        Self {
+            title: payload.title,
+            link: payload.link,
+            city: payload.city,
+            domain: payload.domain,
+            description: payload.description,
+            salary: payload.salary,
+            currency: payload.currency,
+            salary_period: payload.salary_period,
+            start_date: payload.start_date,
+            end_date: payload.end_date,
+            status: payload.status,
+            positions: payload.positions,
+            company_id: payload.company_id,
+            publish_at: payload.publish_at,
+            skills: payload.skills,
+            latitude: payload.latitude,
+            longitude: payload.longitude,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -29,21 +142,155 @@ pub struct UpdateOfferRequest {
     pub link: Option<String>,
     pub city: Option<String>,
     pub domain: Option<String>,
+    pub description: Option<String>,
     pub salary: Option<f64>,
+    pub currency: Option<String>,
+    pub salary_period: Option<SalaryPeriod>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    pub positions: Option<u32>,
     pub available: Option<bool>,
+    pub company_id: Option<String>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub skills: Option<Vec<String>>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
+/// Filters shared by listing, facets and export.
 #[derive(Deserialize)]
-pub struct ListParams {
+pub struct OfferFilterParams {
     pub domain: Option<String>,
     pub city: Option<String>,
+    pub company_id: Option<String>,
+    #[serde(default, deserialize_with = "query_value")]
+    pub min_salary: Option<f64>,
+    #[serde(default, deserialize_with = "query_value")]
+    pub max_salary: Option<f64>,
+    pub start_from: Option<NaiveDate>,
+    pub end_until: Option<NaiveDate>,
+    pub status: Option<OfferStatus>,
+    #[serde(default, deserialize_with = "query_value")]
+    pub available: Option<bool>,
+    /// `lat,lon`, together with `radius_km`.
+    pub near: Option<String>,
+    #[serde(default, deserialize_with = "query_value")]
+    pub radius_km: Option<f64>,
+    /// Keywords searched in titles and descriptions.
+    pub q: Option<String>,
+    /// Comma-separated skills, matched according to `skill_match`.
+    pub skills: Option<String>,
+    #[serde(default)]
+    pub skill_match: SkillMatch,
+}
+
+/// Query strings only hold text, which `#[serde(flatten)]` passes on as is
+/// instead of letting the field parse it.
+fn query_value<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr + Deserialize<'de>,
+    T::Err: std::fmt::Display,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value<T> {
+        Typed(T),
+        Text(String),
+    }
+
+    match Option::<Value<T>>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Value::Typed(value)) => Ok(Some(value)),
+        Some(Value::Text(text)) => text.parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+impl OfferFilterParams {
+    fn into_query(self) -> Result<OfferQuery, OfferError> {
+        Ok(OfferQuery {
+            city: self.city,
+            company_id: self.company_id,
+            domain: self.domain,
+            min_salary: self.min_salary,
+            max_salary: self.max_salary,
+            start_from: self.start_from,
+            end_until: self.end_until,
+            status: self.status,
+            available: self.available,
+            near: parse_near(self.near.as_deref(), self.radius_km)?,
+            text: self.q,
+            skills: self
+                .skills
+                .as_deref()
+                .map(|raw| split_skills(raw, ','))
+                .unwrap_or_default(),
+            skill_match: self.skill_match,
+            include_scheduled: false,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ListParams {
+    #[serde(flatten)]
+    pub filters: OfferFilterParams,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default = "default_limit")]
+    pub limit: u64,
+    pub sort: Option<OfferSort>,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+fn default_limit() -> u64 {
+    20
+}
+
+/// Combines `near=lat,lon` with `radius_km`; one without the other is an error.
+fn parse_near(near: Option<&str>, radius_km: Option<f64>) -> Result<Option<GeoNear>, OfferError> {
+    match (near, radius_km) {
+        (None, None) => Ok(None),
+        (Some(near), Some(radius_km)) => Ok(Some(GeoNear {
+            center: near.parse().map_err(OfferError::InvalidData)?,
+            radius_km,
+        })),
+        _ => Err(OfferError::InvalidData(
+            "near and radius_km must be given together".to_string(),
+        )),
+    }
+}
+
+impl ListParams {
+    fn into_parts(self) -> Result<(OfferQuery, PageRequest), OfferError> {
+        let page = PageRequest {
+            offset: self.offset,
+            limit: self.limit,
+            sort: self.sort,
+            order: self.order,
+        };
+        Ok((self.filters.into_query()?, page))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TransitionRequest {
+    pub status: OfferStatus,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ReservationResponse {
+    Reserved { offer: Box<Offer> },
+    AlreadyTaken,
 }
 
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<FieldError>,
 }
 
 impl From<OfferError> for Response {
@@ -51,6 +298,25 @@ impl From<OfferError> for Response {
         let (status, message) = match err {
             OfferError::NotFound => (StatusCode::NOT_FOUND, "Offer not found".to_string()),
             OfferError::InvalidData(msg) => (StatusCode::BAD_REQUEST, msg),
+            err @ OfferError::VersionMismatch { .. } => {
+                (StatusCode::PRECONDITION_FAILED, err.to_string())
+            }
+            OfferError::InvalidTransition { from, to } => (
+                StatusCode::CONFLICT,
+                format!("Cannot move offer from {} to {}", from, to),
+            ),
+            err @ OfferError::NoSeatsLeft => (StatusCode::CONFLICT, err.to_string()),
+            err @ OfferError::Duplicate { .. } => (StatusCode::CONFLICT, err.to_string()),
+            err @ OfferError::IdempotencyKeyReused => {
+                (StatusCode::UNPROCESSABLE_ENTITY, err.to_string())
+            }
+            OfferError::ValidationFailed(fields) => {
+                let body = ErrorResponse {
+                    error: "Invalid offer".to_string(),
+                    fields,
+                };
+                return (StatusCode::BAD_REQUEST, Json(body)).into_response();
+            }
             OfferError::DatabaseError(msg) => {
                 tracing::error!("Database error: {}", msg);
                 (
@@ -59,31 +325,140 @@ impl From<OfferError> for Response {
                 )
             }
         };
-        (status, Json(ErrorResponse { error: message })).into_response()
+        (
+            status,
+            Json(ErrorResponse {
+                error: message,
+                fields: Vec::new(),
+            }),
+        )
+            .into_response()
     }
 }
 
 type AppResult<T> = Result<T, Response>;
 
-pub struct AppState<R: OfferRepository> {
-    pub service: Arc<OfferService<R>>,
-    pub publisher: Option<Arc<AmqpPublisher>>,
+/// Who makes a change, from the optional `X-Actor` header, recorded in the offer history.
+pub struct Actor(Option<String>);
+
+impl Actor {
+    fn as_deref(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Actor {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let actor = parts
+            .headers
+            .get("x-actor")
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string);
+        Ok(Actor(actor))
+    }
+}
+
+/// Versions from an `If-Match` header. `*` and a missing header accept any
+/// version; weak or malformed tags never match.
+pub struct IfMatch(Option<Vec<u64>>);
+
+impl IfMatch {
+    fn versions(&self) -> Option<&[u64]> {
+        self.0.as_deref()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(header::IF_MATCH) else {
+            return Ok(IfMatch(None));
+        };
+        let value = value.to_str().unwrap_or_default().trim();
+        if value == "*" {
+            return Ok(IfMatch(None));
+        }
+        let versions = value
+            .split(',')
+            .filter_map(|tag| {
+                tag.trim()
+                    .strip_prefix('"')?
+                    .strip_suffix('"')?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        Ok(IfMatch(Some(versions)))
+    }
+}
+
+/// The `Idempotency-Key` header, which makes retrying a creation safe.
+pub struct IdempotencyKey(Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for IdempotencyKey {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let key = parts
+            .headers
+            .get("idempotency-key")
+            .map(|value| value.to_str().unwrap_or_default().trim().to_string());
+        Ok(IdempotencyKey(key))
+    }
+}
+
+/// Strong entity tag of the offer's current version.
+fn etag(offer: &Offer) -> [(header::HeaderName, String); 1] {
+    [(header::ETAG, format!("\"{}\"", offer.version))]
 }
 
-pub fn router<R: OfferRepository + 'static>(
-    service: Arc<OfferService<R>>,
-    publisher: Option<Arc<AmqpPublisher>>,
+pub struct AppState<R: OfferRepository, C: CompanyRepository> {
+    pub service: Arc<OfferService<R, C>>,
+    pub companies: Arc<CompanyService<R, C>>,
+}
+
+pub fn router<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    service: Arc<OfferService<R, C>>,
+    companies: Arc<CompanyService<R, C>>,
 ) -> Router {
-    let state = Arc::new(AppState { service, publisher });
+    let state = Arc::new(AppState { service, companies });
 
     Router::new()
         .route("/health", get(health))
-        .route("/offer", post(create_offer::<R>).get(list_offers::<R>))
+        .route(
+            "/offer",
+            post(create_offer::<R, C>).get(list_offers::<R, C>),
+        )
         .route(
             "/offer/{id}",
-            get(get_offer::<R>)
-                .put(update_offer::<R>)
-                .delete(delete_offer::<R>),
+            get(get_offer::<R, C>)
+                .put(update_offer::<R, C>)
+                .delete(delete_offer::<R, C>),
+        )
+        .route("/offer/{id}/transition", post(transition_offer::<R, C>))
+        .route("/offer/{id}/reserve", post(reserve_offer::<R, C>))
+        .route("/offer/{id}/history", get(offer_history::<R, C>))
+        .route("/offer/{id}/restore", post(restore_offer::<R, C>))
+        .route(
+            "/company",
+            post(companies::create_company::<R, C>).get(companies::list_companies::<R, C>),
+        )
+        .route(
+            "/company/{id}",
+            get(companies::get_company::<R, C>)
+                .put(companies::update_company::<R, C>)
+                .delete(companies::delete_company::<R, C>),
+        )
+        .route("/offers/facets", get(offer_facets::<R, C>))
+        .route("/offers/export", get(export_offers::<R, C>))
+        .route(
+            "/offers/import",
+            post(import_offers::<R, C>).layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
         )
         .with_state(state)
 }
@@ -92,116 +467,236 @@ async fn health() -> impl IntoResponse {
     Json(serde_json::json!({ "status": "ok" }))
 }
 
-async fn create_offer<R: OfferRepository + 'static>(
-    State(state): State<Arc<AppState<R>>>,
+/// With an `Idempotency-Key`, a retry of the same request gets the original
+/// response again, marked with `Idempotent-Replayed: true`.
+async fn create_offer<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    actor: Actor,
+    IdempotencyKey(key): IdempotencyKey,
     Json(payload): Json<CreateOfferRequest>,
-) -> AppResult<(StatusCode, Json<Offer>)> {
-    let params = CreateOfferParams {
-        title: payload.title,
-        link: payload.link,
-        city: payload.city,
-        domain: payload.domain,
-        salary: payload.salary,
-        start_date: payload.start_date,
-        end_date: payload.end_date,
+) -> AppResult<Response> {
+    let Some(key) = key else {
+        let offer = state
+            .service
+            .create_offer(payload.into(), actor.as_deref())
+            .await
+            .map_err(Response::from)?;
+        return Ok((StatusCode::CREATED, Json(offer)).into_response());
     };
-    let offer = state
+
+    // Re-serialized rather than taken verbatim, so that formatting and key
+    // order do not tell retries apart.
+    let request = serde_json::to_string(&payload)
+        .map_err(|e| Response::from(OfferError::InvalidData(e.to_string())))?;
+    let created = state
         .service
-        .create_offer(params)
+        .create_offer_idempotent(payload.into(), &key, &request, actor.as_deref())
         .await
         .map_err(Response::from)?;
+    Ok((
+        StatusCode::CREATED,
+        [("idempotent-replayed", created.replayed.to_string())],
+        Json(created.offer),
+    )
+        .into_response())
+}
 
-    // Publish offer.created event
-    if let Some(publisher) = &state.publisher {
-        let event = serde_json::json!({
-            "id": offer.id,
-            "title": offer.title,
-            "city": offer.city,
-            "domain": offer.domain,
-            "salary": offer.salary,
-        });
-        if let Ok(payload) = serde_json::to_vec(&event) {
-            let pub_clone = publisher.clone();
-            tokio::spawn(async move {
-                if let Err(e) = pub_clone.publish("offer.created", &payload).await {
-                    tracing::error!("Failed to publish offer.created: {}", e);
-                } else {
-                    tracing::info!("Published offer.created event");
-                }
-            });
-        }
-    }
+/// Accepts a JSON array, NDJSON or CSV (with a header row) depending on `Content-Type`.
+async fn import_offers<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    actor: Actor,
+    headers: HeaderMap,
+    body: String,
+) -> AppResult<Json<ImportReport>> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let Some(format) = ImportFormat::from_content_type(content_type) else {
+        let body = ErrorResponse {
+            error: "Content-Type must be application/json, application/x-ndjson or text/csv"
+                .to_string(),
+            fields: Vec::new(),
+        };
+        return Err((StatusCode::UNSUPPORTED_MEDIA_TYPE, Json(body)).into_response());
+    };
 
-    Ok((StatusCode::CREATED, Json(offer)))
+    let rows = import::parse_rows(format, &body)
+        .map_err(|e| Response::from(OfferError::InvalidData(e)))?;
+    Ok(Json(
+        state.service.import_offers(rows, actor.as_deref()).await,
+    ))
 }
 
-async fn get_offer<R: OfferRepository + 'static>(
-    State(state): State<Arc<AppState<R>>>,
+/// Takes the same filters as `GET /offer`; paging and sorting are ignored.
+async fn offer_facets<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Query(params): Query<OfferFilterParams>,
+) -> AppResult<Json<OfferFacets>> {
+    let query = params.into_query().map_err(Response::from)?;
+    let facets = state
+        .service
+        .offer_facets(query)
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(facets))
+}
+
+async fn export_offers<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Query(params): Query<ExportParams>,
+) -> AppResult<Response> {
+    let offers = state
+        .service
+        .export_offers(params.filters.into_query().map_err(Response::from)?)
+        .await
+        .map_err(Response::from)?;
+
+    let format = params.format;
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", format.file_name()),
+            ),
+        ],
+        Body::from_stream(
+            export::encode(format, offers)
+                .inspect_err(|e| tracing::error!("Offer export failed: {}", e)),
+        ),
+    )
+        .into_response())
+}
+
+async fn get_offer<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
     Path(id): Path<String>,
-) -> AppResult<Json<Offer>> {
+) -> AppResult<([(header::HeaderName, String); 1], Json<Offer>)> {
     let offer = state.service.get_offer(&id).await.map_err(Response::from)?;
-    Ok(Json(offer))
+    Ok((etag(&offer), Json(offer)))
 }
 
-async fn list_offers<R: OfferRepository + 'static>(
-    State(state): State<Arc<AppState<R>>>,
+async fn list_offers<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
     Query(params): Query<ListParams>,
-) -> AppResult<Json<Vec<Offer>>> {
-    let offers = if let Some(domain) = params.domain {
-        state
-            .service
-            .list_offers_by_domain(&domain)
-            .await
-            .map_err(Response::from)?
-    } else if let Some(city) = params.city {
-        state
-            .service
-            .list_offers_by_city(&city)
-            .await
-            .map_err(Response::from)?
+) -> AppResult<Json<OfferPage>> {
+    let (query, page) = params.into_parts().map_err(Response::from)?;
+
+    let page = if query.has_criteria() || query.status.is_some() || query.available.is_some() {
+        state.service.search_offers(query, page).await
     } else {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                error: "Query parameter 'domain' or 'city' is required".to_string(),
-            }),
-        )
-            .into_response());
-    };
+        state.service.browse_offers(page).await
+    }
+    .map_err(Response::from)?;
 
-    Ok(Json(offers))
+    Ok(Json(page))
 }
 
-async fn update_offer<R: OfferRepository + 'static>(
-    State(state): State<Arc<AppState<R>>>,
+async fn update_offer<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
     Path(id): Path<String>,
+    actor: Actor,
+    if_match: IfMatch,
     Json(payload): Json<UpdateOfferRequest>,
-) -> AppResult<Json<Offer>> {
+) -> AppResult<([(header::HeaderName, String); 1], Json<Offer>)> {
     let params = UpdateOfferParams {
         title: payload.title,
         link: payload.link,
         city: payload.city,
         domain: payload.domain,
+        description: payload.description,
         salary: payload.salary,
+        currency: payload.currency,
+        salary_period: payload.salary_period,
         start_date: payload.start_date,
         end_date: payload.end_date,
+        positions: payload.positions,
         available: payload.available,
+        company_id: payload.company_id,
+        publish_at: payload.publish_at,
+        skills: payload.skills,
+        latitude: payload.latitude,
+        longitude: payload.longitude,
     };
+    let update = state
+        .service
+        .update_offer(&id, params, if_match.versions(), actor.as_deref())
+        .await
+        .map_err(Response::from)?;
+    Ok((etag(&update.offer), Json(update.offer)))
+}
+
+async fn transition_offer<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Path(id): Path<String>,
+    actor: Actor,
+    Json(payload): Json<TransitionRequest>,
+) -> AppResult<Json<Offer>> {
+    let update = state
+        .service
+        .transition_offer(&id, payload.status, actor.as_deref())
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(update.offer))
+}
+
+async fn reserve_offer<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Path(id): Path<String>,
+    actor: Actor,
+) -> AppResult<(StatusCode, Json<ReservationResponse>)> {
+    let reservation = state
+        .service
+        .reserve_offer(&id, actor.as_deref())
+        .await
+        .map_err(Response::from)?;
+
+    Ok(match reservation {
+        Reservation::Reserved(offer) => (
+            StatusCode::OK,
+            Json(ReservationResponse::Reserved { offer }),
+        ),
+        Reservation::AlreadyTaken => (
+            StatusCode::CONFLICT,
+            Json(ReservationResponse::AlreadyTaken),
+        ),
+    })
+}
+
+async fn offer_history<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Path(id): Path<String>,
+) -> AppResult<Json<Vec<HistoryEntry>>> {
+    let history = state
+        .service
+        .offer_history(&id)
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(history))
+}
+
+async fn restore_offer<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
+    Path(id): Path<String>,
+    actor: Actor,
+) -> AppResult<Json<Offer>> {
     let offer = state
         .service
-        .update_offer(&id, params)
+        .restore_offer(&id, actor.as_deref())
         .await
         .map_err(Response::from)?;
     Ok(Json(offer))
 }
 
-async fn delete_offer<R: OfferRepository + 'static>(
-    State(state): State<Arc<AppState<R>>>,
+async fn delete_offer<R: OfferRepository + 'static, C: CompanyRepository + 'static>(
+    State(state): State<Arc<AppState<R, C>>>,
     Path(id): Path<String>,
+    actor: Actor,
 ) -> AppResult<StatusCode> {
     state
         .service
-        .delete_offer(&id)
+        .delete_offer(&id, actor.as_deref())
         .await
         .map_err(Response::from)?;
     Ok(StatusCode::OK)
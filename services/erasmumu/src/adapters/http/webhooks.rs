@@ -0,0 +1,201 @@
+use super::{AppResult, ErrorResponse};
+use crate::application::webhook_service::{
+    CreateSubscriptionParams, UpdateSubscriptionParams, WebhookService,
+};
+use crate::domain::ports::webhook_repository::{WebhookError, WebhookRepository};
+use crate::domain::ports::webhook_sender::WebhookSender;
+use crate::domain::webhook::{WebhookDelivery, WebhookSubscription};
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub struct CreateSubscriptionRequest {
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+    pub secret: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateSubscriptionRequest {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub secret: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DeliveriesParams {
+    #[serde(default = "default_limit")]
+    pub limit: u64,
+}
+
+fn default_limit() -> u64 {
+    20
+}
+
+/// A subscription as listed back, without its secret.
+#[derive(Serialize)]
+pub struct SubscriptionResponse {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    /// Only returned when the secret is set, so that it can be kept.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+impl SubscriptionResponse {
+    fn new(subscription: WebhookSubscription, with_secret: bool) -> Self {
+        Self {
+            id: subscription.id,
+            url: subscription.url,
+            events: subscription.events,
+            created_at: subscription.created_at,
+            secret: with_secret.then_some(subscription.secret),
+        }
+    }
+}
+
+impl From<WebhookError> for Response {
+    fn from(err: WebhookError) -> Self {
+        let (status, message, fields) = match err {
+            WebhookError::NotFound => (
+                StatusCode::NOT_FOUND,
+                "Webhook subscription not found".to_string(),
+                Vec::new(),
+            ),
+            WebhookError::ValidationFailed(fields) => (
+                StatusCode::BAD_REQUEST,
+                "Invalid webhook subscription".to_string(),
+                fields,
+            ),
+            WebhookError::DatabaseError(msg) => {
+                tracing::error!("Database error: {}", msg);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error".to_string(),
+                    Vec::new(),
+                )
+            }
+        };
+        (
+            status,
+            Json(ErrorResponse {
+                error: message,
+                fields,
+            }),
+        )
+            .into_response()
+    }
+}
+
+pub fn router<W: WebhookRepository + 'static, S: WebhookSender + 'static>(
+    service: Arc<WebhookService<W, S>>,
+) -> Router {
+    Router::new()
+        .route(
+            "/webhook",
+            get(list_subscriptions::<W, S>).post(create_subscription::<W, S>),
+        )
+        .route(
+            "/webhook/{id}",
+            get(get_subscription::<W, S>)
+                .put(update_subscription::<W, S>)
+                .delete(delete_subscription::<W, S>),
+        )
+        .route("/webhook/{id}/deliveries", get(list_deliveries::<W, S>))
+        .with_state(service)
+}
+
+async fn create_subscription<W: WebhookRepository + 'static, S: WebhookSender + 'static>(
+    State(service): State<Arc<WebhookService<W, S>>>,
+    Json(payload): Json<CreateSubscriptionRequest>,
+) -> AppResult<(StatusCode, Json<SubscriptionResponse>)> {
+    let subscription = service
+        .create_subscription(CreateSubscriptionParams {
+            url: payload.url,
+            events: payload.events,
+            secret: payload.secret,
+        })
+        .await
+        .map_err(Response::from)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(SubscriptionResponse::new(subscription, true)),
+    ))
+}
+
+async fn list_subscriptions<W: WebhookRepository + 'static, S: WebhookSender + 'static>(
+    State(service): State<Arc<WebhookService<W, S>>>,
+) -> AppResult<Json<Vec<SubscriptionResponse>>> {
+    let subscriptions = service.list_subscriptions().await.map_err(Response::from)?;
+    Ok(Json(
+        subscriptions
+            .into_iter()
+            .map(|subscription| SubscriptionResponse::new(subscription, false))
+            .collect(),
+    ))
+}
+
+async fn get_subscription<W: WebhookRepository + 'static, S: WebhookSender + 'static>(
+    State(service): State<Arc<WebhookService<W, S>>>,
+    Path(id): Path<String>,
+) -> AppResult<Json<SubscriptionResponse>> {
+    let subscription = service
+        .get_subscription(&id)
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(SubscriptionResponse::new(subscription, false)))
+}
+
+async fn update_subscription<W: WebhookRepository + 'static, S: WebhookSender + 'static>(
+    State(service): State<Arc<WebhookService<W, S>>>,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateSubscriptionRequest>,
+) -> AppResult<Json<SubscriptionResponse>> {
+    let rotated = payload.secret.is_some();
+    let subscription = service
+        .update_subscription(
+            &id,
+            UpdateSubscriptionParams {
+                url: payload.url,
+                events: payload.events,
+                secret: payload.secret,
+            },
+        )
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(SubscriptionResponse::new(subscription, rotated)))
+}
+
+async fn delete_subscription<W: WebhookRepository + 'static, S: WebhookSender + 'static>(
+    State(service): State<Arc<WebhookService<W, S>>>,
+    Path(id): Path<String>,
+) -> AppResult<StatusCode> {
+    service
+        .delete_subscription(&id)
+        .await
+        .map_err(Response::from)?;
+    Ok(StatusCode::OK)
+}
+
+async fn list_deliveries<W: WebhookRepository + 'static, S: WebhookSender + 'static>(
+    State(service): State<Arc<WebhookService<W, S>>>,
+    Path(id): Path<String>,
+    Query(params): Query<DeliveriesParams>,
+) -> AppResult<Json<Vec<WebhookDelivery>>> {
+    let deliveries = service
+        .deliveries(&id, params.limit)
+        .await
+        .map_err(Response::from)?;
+    Ok(Json(deliveries))
+}
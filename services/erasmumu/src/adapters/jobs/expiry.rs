@@ -0,0 +1,29 @@
+use crate::application::expiry_sweeper::ExpirySweeper;
+use crate::domain::ports::clock::Clock;
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::OfferRepository;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn_expiry_job<R, C, K>(sweeper: ExpirySweeper<R, C, K>, every: Duration) -> JoinHandle<()>
+where
+    R: OfferRepository + 'static,
+    C: CompanyRepository + 'static,
+    K: Clock + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+
+            // offer.expired events go through the outbox with each update.
+            match sweeper.sweep().await {
+                Ok(expired) if !expired.is_empty() => {
+                    tracing::info!("Expired {} offer(s)", expired.len());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Offer expiry sweep failed: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,5 @@
+pub mod expiry;
+pub mod outbox_relay;
+pub mod publication;
+pub mod purge;
+pub mod webhooks;
@@ -0,0 +1,87 @@
+use crate::adapters::amqp::publisher::AmqpPublisher;
+use crate::domain::ports::outbox_repository::OutboxRepository;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+const BATCH_SIZE: i64 = 100;
+
+/// Relays outbox messages to RabbitMQ in the order they were recorded. The
+/// connection is (re)opened lazily, so messages simply wait in MongoDB while
+/// the broker is unreachable and go out once it is back.
+pub fn spawn_outbox_relay<O>(outbox: O, amqp_url: String, every: Duration) -> JoinHandle<()>
+where
+    O: OutboxRepository + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        let mut publisher: Option<AmqpPublisher> = None;
+        loop {
+            ticker.tick().await;
+
+            let pending = match outbox.pending(BATCH_SIZE).await {
+                Ok(pending) => pending,
+                Err(e) => {
+                    tracing::error!("Failed to read the outbox: {}", e);
+                    continue;
+                }
+            };
+            if pending.is_empty() {
+                continue;
+            }
+
+            if publisher.is_none() {
+                match AmqpPublisher::new(&amqp_url).await {
+                    Ok(p) => {
+                        tracing::info!("Connected to RabbitMQ");
+                        publisher = Some(p);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "RabbitMQ unreachable, {} event(s) waiting in the outbox: {}",
+                            pending.len(),
+                            e
+                        );
+                        continue;
+                    }
+                }
+            }
+            let Some(channel) = &publisher else {
+                continue;
+            };
+
+            let mut reconnect = false;
+            for message in pending {
+                let payload = match serde_json::to_vec(&message.payload) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        tracing::error!("Failed to encode outbox message {}: {}", message.id, e);
+                        continue;
+                    }
+                };
+
+                if let Err(e) = channel.publish(&message.routing_key, &payload).await {
+                    tracing::error!("Failed to publish {}: {}", message.routing_key, e);
+                    if let Err(e) = outbox.record_failure(&message.id, &e.to_string()).await {
+                        tracing::error!("Failed to record outbox failure: {}", e);
+                    }
+                    // Reconnect on the next tick and resume from this message to keep ordering.
+                    reconnect = true;
+                    break;
+                }
+
+                if let Err(e) = outbox.mark_sent(&message.id).await {
+                    tracing::error!(
+                        "Failed to mark outbox message {} as sent: {}",
+                        message.id,
+                        e
+                    );
+                    break;
+                }
+                tracing::info!("Published {} event", message.routing_key);
+            }
+            if reconnect {
+                publisher = None;
+            }
+        }
+    })
+}
@@ -0,0 +1,32 @@
+use crate::application::publication_scheduler::PublicationScheduler;
+use crate::domain::ports::clock::Clock;
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::OfferRepository;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn_publication_job<R, C, K>(
+    scheduler: PublicationScheduler<R, C, K>,
+    every: Duration,
+) -> JoinHandle<()>
+where
+    R: OfferRepository + 'static,
+    C: CompanyRepository + 'static,
+    K: Clock + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+
+            // offer.created events go through the outbox with each update.
+            match scheduler.publish().await {
+                Ok(published) if !published.is_empty() => {
+                    tracing::info!("Published {} scheduled offer(s)", published.len());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Scheduled offer publication failed: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,26 @@
+use crate::application::tombstone_purger::TombstonePurger;
+use crate::domain::ports::clock::Clock;
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::OfferRepository;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+pub fn spawn_purge_job<R, C, K>(purger: TombstonePurger<R, C, K>, every: Duration) -> JoinHandle<()>
+where
+    R: OfferRepository + 'static,
+    C: CompanyRepository + 'static,
+    K: Clock + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+
+            match purger.purge().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("Purged {} deleted offer(s)", purged),
+                Err(e) => tracing::error!("Deleted offer purge failed: {}", e),
+            }
+        }
+    })
+}
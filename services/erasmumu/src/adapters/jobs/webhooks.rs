@@ -0,0 +1,72 @@
+use crate::application::webhook_service::WebhookService;
+use crate::domain::ports::outbox_repository::OutboxRepository;
+use crate::domain::ports::webhook_repository::WebhookRepository;
+use crate::domain::ports::webhook_sender::WebhookSender;
+use crate::domain::webhook::DeliveryStatus;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+const BATCH_SIZE: i64 = 100;
+
+/// Hands outbox messages to webhook subscriptions, independently of the
+/// RabbitMQ relay, then sends the deliveries that are due.
+pub fn spawn_webhook_job<O, W, S>(
+    outbox: O,
+    service: Arc<WebhookService<W, S>>,
+    every: Duration,
+) -> JoinHandle<()>
+where
+    O: OutboxRepository + 'static,
+    W: WebhookRepository + 'static,
+    S: WebhookSender + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+
+            match outbox.pending_fan_out(BATCH_SIZE).await {
+                Ok(pending) => {
+                    for message in pending {
+                        if let Err(e) = service.fan_out(&message, Utc::now()).await {
+                            tracing::error!("Failed to queue webhooks for {}: {}", message.id, e);
+                            break;
+                        }
+                        if let Err(e) = outbox.mark_fanned_out(&message.id).await {
+                            tracing::error!(
+                                "Failed to mark outbox message {} as fanned out: {}",
+                                message.id,
+                                e
+                            );
+                            break;
+                        }
+                    }
+                }
+                Err(e) => tracing::error!("Failed to read the outbox: {}", e),
+            }
+
+            match service.deliver_due(Utc::now()).await {
+                Ok(attempted) => {
+                    for delivery in attempted {
+                        match delivery.status {
+                            DeliveryStatus::Delivered => {}
+                            DeliveryStatus::Pending => tracing::warn!(
+                                "Webhook delivery {} failed, retrying at {:?}",
+                                delivery.id,
+                                delivery.next_attempt_at
+                            ),
+                            DeliveryStatus::Failed => tracing::error!(
+                                "Gave up on webhook delivery {} after {} attempt(s)",
+                                delivery.id,
+                                delivery.attempts.len()
+                            ),
+                        }
+                    }
+                }
+                Err(e) => tracing::error!("Webhook delivery failed: {}", e),
+            }
+        }
+    })
+}
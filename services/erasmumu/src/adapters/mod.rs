@@ -1,3 +1,5 @@
 pub mod amqp;
 pub mod http;
+pub mod jobs;
 pub mod persistence;
+pub mod webhook;
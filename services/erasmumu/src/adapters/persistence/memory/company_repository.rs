@@ -0,0 +1,59 @@
+use crate::domain::company::Company;
+use crate::domain::ports::company_repository::{CompanyError, CompanyRepository};
+use std::sync::{Mutex, MutexGuard};
+
+/// Keeps companies in memory, behaving like
+/// [`MongoCompanyRepository`](crate::adapters::persistence::mongo::company_repository::MongoCompanyRepository).
+#[derive(Default)]
+pub struct InMemoryCompanyRepository {
+    companies: Mutex<Vec<Company>>,
+}
+
+impl InMemoryCompanyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Company>> {
+        self.companies
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl CompanyRepository for InMemoryCompanyRepository {
+    async fn create(&self, company: &Company) -> Result<Company, CompanyError> {
+        self.lock().push(company.clone());
+        Ok(company.clone())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<Company>, CompanyError> {
+        Ok(self.lock().iter().find(|company| company.id == id).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Company>, CompanyError> {
+        let mut companies = self.lock().clone();
+        companies.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(companies)
+    }
+
+    async fn update(&self, company: &Company) -> Result<Company, CompanyError> {
+        let mut companies = self.lock();
+        let stored = companies
+            .iter_mut()
+            .find(|stored| stored.id == company.id)
+            .ok_or(CompanyError::NotFound)?;
+        *stored = company.clone();
+        Ok(company.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), CompanyError> {
+        let mut companies = self.lock();
+        let count = companies.len();
+        companies.retain(|company| company.id != id);
+        if companies.len() == count {
+            return Err(CompanyError::NotFound);
+        }
+        Ok(())
+    }
+}
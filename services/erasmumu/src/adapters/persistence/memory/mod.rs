@@ -0,0 +1,3 @@
+pub mod company_repository;
+pub mod offer_repository;
+pub mod webhook_repository;
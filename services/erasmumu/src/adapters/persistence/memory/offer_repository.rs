@@ -0,0 +1,464 @@
+use crate::domain::facets::{
+    FacetCount, OfferFacets, SALARY_BUCKET_BOUNDS, by_count, salary_bucket, salary_buckets,
+};
+use crate::domain::history::{ChangeLog, HistoryEntry};
+use crate::domain::idempotency::IdempotencyRecord;
+use crate::domain::offer::{
+    ExpiryReference, Offer, OfferPage, OfferQuery, OfferSort, OfferStatus, OfferUpdate,
+    PageRequest, SkillMatch, SortOrder,
+};
+use crate::domain::outbox::OutboxMessage;
+use crate::domain::ports::offer_repository::{OfferError, OfferRepository};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::StreamExt;
+use futures_util::stream::{self, BoxStream};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// Keeps offers, outbox messages and history in memory, behaving like
+/// [`MongoOfferRepository`] for tests and local experiments. Every write holds
+/// a single lock, which makes it as atomic as a transaction.
+///
+/// [`MongoOfferRepository`]: crate::adapters::persistence::mongo::offer_repository::MongoOfferRepository
+#[derive(Default)]
+pub struct InMemoryOfferRepository {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// In insertion order, which stands in for MongoDB's ObjectIds.
+    offers: Vec<Offer>,
+    outbox: Vec<OutboxMessage>,
+    history: Vec<HistoryEntry>,
+    idempotency: Vec<IdempotencyRecord>,
+}
+
+impl State {
+    fn record(&mut self, log: &ChangeLog) {
+        self.outbox
+            .extend(log.events.iter().map(OutboxMessage::new));
+        self.history.extend(log.history.iter().cloned());
+    }
+
+    fn find_duplicate(&self, offer: &Offer) -> Option<&Offer> {
+        self.offers
+            .iter()
+            .find(|stored| stored.id != offer.id && stored.duplicates(offer))
+    }
+
+    /// Fails like the unique indexes of [`MongoOfferRepository`] would.
+    ///
+    /// [`MongoOfferRepository`]: crate::adapters::persistence::mongo::offer_repository::MongoOfferRepository
+    fn ensure_unique(&self, offer: &Offer) -> Result<(), OfferError> {
+        match self.find_duplicate(offer) {
+            Some(existing) => Err(OfferError::Duplicate {
+                existing_id: existing.id.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl InMemoryOfferRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages written to the outbox so far, oldest first.
+    pub fn outbox(&self) -> Vec<OutboxMessage> {
+        self.lock().outbox.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // A test that panicked while holding the lock leaves consistent data.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Lowercased words, split like MongoDB's text index without a language does.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Occurrences of the searched words, weighted like the text index: three
+/// for the title, one for the description.
+fn relevance(offer: &Offer, text: &str) -> f64 {
+    let terms: Vec<String> = words(text).collect();
+    let occurrences = |field: &str| words(field).filter(|word| terms.contains(word)).count() as f64;
+    3.0 * occurrences(&offer.title) + occurrences(offer.description.as_deref().unwrap_or_default())
+}
+
+fn matches(offer: &Offer, query: &OfferQuery) -> bool {
+    if offer.is_deleted() || (offer.is_scheduled() && !query.include_scheduled) {
+        return false;
+    }
+    if query
+        .text
+        .as_deref()
+        .is_some_and(|text| relevance(offer, text) == 0.0)
+    {
+        return false;
+    }
+    if query.city.as_ref().is_some_and(|city| &offer.city != city)
+        || query
+            .company_id
+            .as_ref()
+            .is_some_and(|id| offer.company_id.as_ref() != Some(id))
+        || query
+            .domain
+            .as_ref()
+            .is_some_and(|domain| &offer.domain != domain)
+        || query.status.is_some_and(|status| offer.status != status)
+        || query
+            .available
+            .is_some_and(|available| offer.available != available)
+        || query
+            .min_salary
+            .is_some_and(|min| offer.salary_monthly_eur < min)
+        || query
+            .max_salary
+            .is_some_and(|max| offer.salary_monthly_eur > max)
+        || query
+            .start_from
+            .is_some_and(|start_from| offer.start_date < start_from)
+        || query
+            .end_until
+            .is_some_and(|end_until| offer.end_date > end_until)
+    {
+        return false;
+    }
+    if !query.skills.is_empty() {
+        let has = |skill: &String| offer.skills.contains(skill);
+        let matched = match query.skill_match {
+            SkillMatch::Any => query.skills.iter().any(has),
+            SkillMatch::All => query.skills.iter().all(has),
+        };
+        if !matched {
+            return false;
+        }
+    }
+    match &query.near {
+        Some(near) => offer
+            .location
+            .is_some_and(|location| location.distance_km(&near.center) <= near.radius_km),
+        None => true,
+    }
+}
+
+fn counts(values: impl Iterator<Item = String>) -> Vec<FacetCount> {
+    let mut counts = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(value, count)| FacetCount { value, count })
+        .collect()
+}
+
+fn compare(a: &Offer, b: &Offer, sort: OfferSort) -> Ordering {
+    match sort {
+        OfferSort::Salary => a.salary_monthly_eur.total_cmp(&b.salary_monthly_eur),
+        OfferSort::StartDate => a.start_date.cmp(&b.start_date),
+        OfferSort::Title => a.title.cmp(&b.title),
+    }
+}
+
+/// Order of search results when no sort is asked for.
+#[derive(Clone, Copy)]
+enum Ranking<'a> {
+    Newest,
+    /// Nearest first, for `near` searches.
+    Distance,
+    /// Most relevant first, for text searches.
+    Relevance(&'a str),
+}
+
+/// Sorts `(insertion index, offer)` pairs like `sort_document` does, newest
+/// first by default and as a tie-break.
+fn sort_matches(matches: &mut [(usize, Offer)], page: &PageRequest, ranking: Ranking) {
+    matches.sort_by(|(a_index, a), (b_index, b)| {
+        let primary = match (page.sort, ranking) {
+            (Some(sort), _) => {
+                let ordering = compare(a, b, sort);
+                match page.order {
+                    SortOrder::Asc => ordering,
+                    SortOrder::Desc => ordering.reverse(),
+                }
+            }
+            (None, Ranking::Distance) => a
+                .distance_km
+                .unwrap_or_default()
+                .total_cmp(&b.distance_km.unwrap_or_default()),
+            (None, Ranking::Relevance(text)) => relevance(b, text).total_cmp(&relevance(a, text)),
+            (None, Ranking::Newest) => Ordering::Equal,
+        };
+        primary.then(b_index.cmp(a_index))
+    });
+}
+
+fn paginate(mut matches: Vec<(usize, Offer)>, page: &PageRequest, ranking: Ranking) -> OfferPage {
+    sort_matches(&mut matches, page, ranking);
+    let total = matches.len() as u64;
+    let offers = matches
+        .into_iter()
+        .skip(page.offset as usize)
+        .take(page.limit as usize)
+        .map(|(_, offer)| offer)
+        .collect();
+    OfferPage {
+        offers,
+        total,
+        offset: page.offset,
+        limit: page.limit,
+    }
+}
+
+impl OfferRepository for InMemoryOfferRepository {
+    async fn create(&self, offer: &Offer, log: &ChangeLog) -> Result<Offer, OfferError> {
+        let mut state = self.lock();
+        state.ensure_unique(offer)?;
+        state.offers.push(offer.clone());
+        state.record(log);
+        Ok(offer.clone())
+    }
+
+    async fn create_idempotent(
+        &self,
+        offer: &Offer,
+        log: &ChangeLog,
+        record: &IdempotencyRecord,
+    ) -> Result<Offer, OfferError> {
+        let mut state = self.lock();
+        if state
+            .idempotency
+            .iter()
+            .any(|stored| stored.key == record.key)
+        {
+            return Err(OfferError::IdempotencyKeyReused);
+        }
+        state.ensure_unique(offer)?;
+        state.offers.push(offer.clone());
+        state.idempotency.push(record.clone());
+        state.record(log);
+        Ok(offer.clone())
+    }
+
+    async fn find_idempotency_record(
+        &self,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>, OfferError> {
+        let now = Utc::now();
+        Ok(self
+            .lock()
+            .idempotency
+            .iter()
+            .find(|record| record.key == key && !record.is_expired(now))
+            .cloned())
+    }
+
+    async fn create_many(&self, offers: &[Offer], log: &ChangeLog) -> Result<(), OfferError> {
+        let mut state = self.lock();
+        for (index, offer) in offers.iter().enumerate() {
+            state.ensure_unique(offer)?;
+            if let Some(earlier) = offers[..index].iter().find(|other| other.duplicates(offer)) {
+                return Err(OfferError::Duplicate {
+                    existing_id: earlier.id.clone(),
+                });
+            }
+        }
+        state.offers.extend(offers.iter().cloned());
+        state.record(log);
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<Offer>, OfferError> {
+        Ok(self
+            .lock()
+            .offers
+            .iter()
+            .find(|offer| offer.id == id)
+            .cloned())
+    }
+
+    async fn find_duplicate(&self, offer: &Offer) -> Result<Option<Offer>, OfferError> {
+        Ok(self.lock().find_duplicate(offer).cloned())
+    }
+
+    async fn search(
+        &self,
+        query: &OfferQuery,
+        page: &PageRequest,
+    ) -> Result<OfferPage, OfferError> {
+        let matches = self
+            .lock()
+            .offers
+            .iter()
+            .enumerate()
+            .filter(|(_, offer)| matches(offer, query))
+            .map(|(index, offer)| {
+                let mut offer = offer.clone();
+                if let (Some(near), Some(location)) = (&query.near, offer.location) {
+                    offer.distance_km = Some(location.distance_km(&near.center));
+                }
+                (index, offer)
+            })
+            .collect();
+        let ranking = match (&query.near, &query.text) {
+            (Some(_), _) => Ranking::Distance,
+            (None, Some(text)) => Ranking::Relevance(text),
+            (None, None) => Ranking::Newest,
+        };
+        Ok(paginate(matches, page, ranking))
+    }
+
+    async fn facets(&self, query: &OfferQuery) -> Result<OfferFacets, OfferError> {
+        let state = self.lock();
+        let offers: Vec<&Offer> = state
+            .offers
+            .iter()
+            .filter(|offer| matches(offer, query))
+            .collect();
+
+        let mut salaries = [0; SALARY_BUCKET_BOUNDS.len()];
+        for offer in &offers {
+            salaries[salary_bucket(offer.salary_monthly_eur)] += 1;
+        }
+        Ok(OfferFacets {
+            total: offers.len() as u64,
+            cities: by_count(counts(offers.iter().map(|offer| offer.city.clone()))),
+            domains: by_count(counts(offers.iter().map(|offer| offer.domain.clone()))),
+            salaries: salary_buckets(salaries),
+            start_months: counts(
+                offers
+                    .iter()
+                    .map(|offer| offer.start_date.format("%Y-%m").to_string()),
+            ),
+        })
+    }
+
+    async fn stream(
+        &self,
+        query: &OfferQuery,
+    ) -> Result<BoxStream<'static, Result<Offer, OfferError>>, OfferError> {
+        let offers: Vec<Offer> = self
+            .lock()
+            .offers
+            .iter()
+            .filter(|offer| matches(offer, query))
+            .cloned()
+            .collect();
+        Ok(stream::iter(offers.into_iter().map(Ok)).boxed())
+    }
+
+    async fn list_all(&self, page: &PageRequest) -> Result<OfferPage, OfferError> {
+        let matches = self
+            .lock()
+            .offers
+            .iter()
+            .enumerate()
+            .filter(|(_, offer)| {
+                !offer.is_deleted()
+                    && !offer.is_scheduled()
+                    && offer.status == OfferStatus::Published
+            })
+            .map(|(index, offer)| (index, offer.clone()))
+            .collect();
+        Ok(paginate(matches, page, Ranking::Newest))
+    }
+
+    async fn list_past_due(
+        &self,
+        reference: ExpiryReference,
+        today: NaiveDate,
+    ) -> Result<Vec<Offer>, OfferError> {
+        Ok(self
+            .lock()
+            .offers
+            .iter()
+            .filter(|offer| {
+                let date = match reference {
+                    ExpiryReference::StartDate => offer.start_date,
+                    ExpiryReference::EndDate => offer.end_date,
+                };
+                !offer.is_deleted()
+                    && !offer.is_scheduled()
+                    && offer.status == OfferStatus::Published
+                    && date < today
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn list_due_for_publication(&self, now: DateTime<Utc>) -> Result<Vec<Offer>, OfferError> {
+        Ok(self
+            .lock()
+            .offers
+            .iter()
+            .filter(|offer| !offer.is_deleted() && offer.publish_at.is_some_and(|at| at <= now))
+            .cloned()
+            .collect())
+    }
+
+    async fn reserve(&self, id: &str, actor: Option<&str>) -> Result<Option<Offer>, OfferError> {
+        let mut state = self.lock();
+        let Some(stored) = state.offers.iter_mut().find(|offer| {
+            offer.id == id
+                && offer.status == OfferStatus::Published
+                && offer.remaining > 0
+                && !offer.is_deleted()
+                && !offer.is_scheduled()
+        }) else {
+            return Ok(None);
+        };
+
+        let mut after = stored.clone();
+        after.take_seat();
+        let update = OfferUpdate::new(stored, after);
+        *stored = update.offer.clone();
+        state.record(&ChangeLog::updated(&update, actor));
+        Ok(Some(update.offer))
+    }
+
+    async fn update(&self, offer: &Offer, log: &ChangeLog) -> Result<Offer, OfferError> {
+        let mut state = self.lock();
+        let Some(index) = state.offers.iter().position(|stored| stored.id == offer.id) else {
+            return Err(OfferError::NotFound);
+        };
+        let current = state.offers[index].version;
+        if current != offer.version.saturating_sub(1) {
+            return Err(OfferError::VersionMismatch { current });
+        }
+        state.ensure_unique(offer)?;
+        state.offers[index] = offer.clone();
+        state.record(log);
+        Ok(offer.clone())
+    }
+
+    async fn history(&self, offer_id: &str) -> Result<Vec<HistoryEntry>, OfferError> {
+        Ok(self
+            .lock()
+            .history
+            .iter()
+            .filter(|entry| entry.offer_id == offer_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn purge_deleted(&self, before: DateTime<Utc>) -> Result<u64, OfferError> {
+        let mut state = self.lock();
+        let count = state.offers.len();
+        state.offers.retain(|offer| {
+            offer
+                .deleted_at
+                .is_none_or(|deleted_at| deleted_at >= before)
+        });
+        Ok((count - state.offers.len()) as u64)
+    }
+}
@@ -0,0 +1,137 @@
+use crate::domain::ports::webhook_repository::{WebhookError, WebhookRepository};
+use crate::domain::webhook::{DeliveryStatus, WebhookDelivery, WebhookSubscription};
+use chrono::{DateTime, Utc};
+use std::sync::{Mutex, MutexGuard};
+
+/// Keeps subscriptions and deliveries in memory, behaving like
+/// [`MongoWebhookRepository`](crate::adapters::persistence::mongo::webhook_repository::MongoWebhookRepository).
+#[derive(Default)]
+pub struct InMemoryWebhookRepository {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Both in insertion order.
+    subscriptions: Vec<WebhookSubscription>,
+    deliveries: Vec<WebhookDelivery>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn create_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        self.lock().subscriptions.push(subscription.clone());
+        Ok(subscription.clone())
+    }
+
+    async fn get_subscription(
+        &self,
+        id: &str,
+    ) -> Result<Option<WebhookSubscription>, WebhookError> {
+        Ok(self
+            .lock()
+            .subscriptions
+            .iter()
+            .find(|subscription| subscription.id == id)
+            .cloned())
+    }
+
+    async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, WebhookError> {
+        Ok(self.lock().subscriptions.clone())
+    }
+
+    async fn update_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        let mut state = self.lock();
+        let stored = state
+            .subscriptions
+            .iter_mut()
+            .find(|stored| stored.id == subscription.id)
+            .ok_or(WebhookError::NotFound)?;
+        *stored = subscription.clone();
+        Ok(subscription.clone())
+    }
+
+    async fn delete_subscription(&self, id: &str) -> Result<(), WebhookError> {
+        let mut state = self.lock();
+        let count = state.subscriptions.len();
+        state
+            .subscriptions
+            .retain(|subscription| subscription.id != id);
+        if state.subscriptions.len() == count {
+            return Err(WebhookError::NotFound);
+        }
+        state
+            .deliveries
+            .retain(|delivery| delivery.subscription_id != id);
+        Ok(())
+    }
+
+    async fn enqueue(&self, deliveries: &[WebhookDelivery]) -> Result<(), WebhookError> {
+        self.lock().deliveries.extend(deliveries.iter().cloned());
+        Ok(())
+    }
+
+    async fn due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        Ok(self
+            .lock()
+            .deliveries
+            .iter()
+            .filter(|delivery| {
+                delivery.status == DeliveryStatus::Pending
+                    && delivery.next_attempt_at.is_some_and(|at| at <= now)
+            })
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn save_delivery(&self, delivery: &WebhookDelivery) -> Result<(), WebhookError> {
+        let mut state = self.lock();
+        // Its subscription may have been deleted while it was being sent.
+        if let Some(stored) = state
+            .deliveries
+            .iter_mut()
+            .find(|stored| stored.id == delivery.id)
+        {
+            *stored = delivery.clone();
+        }
+        Ok(())
+    }
+
+    async fn deliveries(
+        &self,
+        subscription_id: &str,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        Ok(self
+            .lock()
+            .deliveries
+            .iter()
+            .rev()
+            .filter(|delivery| delivery.subscription_id == subscription_id)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+}
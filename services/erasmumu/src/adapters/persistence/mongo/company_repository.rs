@@ -0,0 +1,71 @@
+use crate::domain::company::Company;
+use crate::domain::ports::company_repository::{CompanyError, CompanyRepository};
+use futures_util::TryStreamExt;
+use mongodb::Collection;
+use mongodb::bson::doc;
+
+pub struct MongoCompanyRepository {
+    collection: Collection<Company>,
+}
+
+impl MongoCompanyRepository {
+    pub fn new(collection: Collection<Company>) -> Self {
+        Self { collection }
+    }
+}
+
+impl CompanyRepository for MongoCompanyRepository {
+    async fn create(&self, company: &Company) -> Result<Company, CompanyError> {
+        self.collection
+            .insert_one(company)
+            .await
+            .map_err(|e: mongodb::error::Error| CompanyError::DatabaseError(e.to_string()))?;
+        Ok(company.clone())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<Company>, CompanyError> {
+        self.collection
+            .find_one(doc! { "id": id })
+            .await
+            .map_err(|e: mongodb::error::Error| CompanyError::DatabaseError(e.to_string()))
+    }
+
+    async fn list(&self) -> Result<Vec<Company>, CompanyError> {
+        let cursor = self
+            .collection
+            .find(doc! {})
+            .sort(doc! { "name": 1, "_id": 1 })
+            .await
+            .map_err(|e: mongodb::error::Error| CompanyError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| CompanyError::DatabaseError(e.to_string()))
+    }
+
+    async fn update(&self, company: &Company) -> Result<Company, CompanyError> {
+        let result = self
+            .collection
+            .replace_one(doc! { "id": &company.id }, company)
+            .await
+            .map_err(|e: mongodb::error::Error| CompanyError::DatabaseError(e.to_string()))?;
+
+        if result.matched_count == 0 {
+            return Err(CompanyError::NotFound);
+        }
+        Ok(company.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), CompanyError> {
+        let result = self
+            .collection
+            .delete_one(doc! { "id": id })
+            .await
+            .map_err(|e: mongodb::error::Error| CompanyError::DatabaseError(e.to_string()))?;
+
+        if result.deleted_count == 0 {
+            return Err(CompanyError::NotFound);
+        }
+        Ok(())
+    }
+}
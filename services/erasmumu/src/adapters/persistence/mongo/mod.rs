@@ -1 +1,4 @@
+pub mod company_repository;
 pub mod offer_repository;
+pub mod outbox_repository;
+pub mod webhook_repository;
@@ -1,26 +1,775 @@
-use crate::domain::offer::Offer;
+use crate::domain::facets::{
+    FacetCount, OfferFacets, SALARY_BUCKET_BOUNDS, by_count, salary_bucket, salary_buckets,
+};
+use crate::domain::geo::{EARTH_RADIUS_KM, GeoNear, known_cities};
+use crate::domain::history::{ChangeLog, HistoryEntry};
+use crate::domain::idempotency::{IDEMPOTENCY_KEY_TTL, IdempotencyRecord};
+use crate::domain::offer::{
+    ExpiryReference, Offer, OfferPage, OfferQuery, OfferStatus, OfferUpdate, PageRequest,
+    SkillMatch, SortOrder,
+};
+use crate::domain::outbox::OutboxMessage;
 use crate::domain::ports::offer_repository::{OfferError, OfferRepository};
-use futures_util::TryStreamExt;
-use mongodb::Collection;
-use mongodb::bson::doc;
+use crate::domain::salary::{BASE_CURRENCY, ExchangeRates, SalaryPeriod};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use futures_util::future::BoxFuture;
+use futures_util::stream::BoxStream;
+use futures_util::{FutureExt, StreamExt, TryStreamExt};
+use mongodb::bson::{self, Bson, Document, doc};
+use mongodb::error::{ErrorKind, TRANSIENT_TRANSACTION_ERROR, WriteFailure};
+use mongodb::options::{Collation, CollationStrength, IndexOptions, ReturnDocument};
+use mongodb::{ClientSession, Collection, IndexModel};
+use serde::{Deserialize, Serialize};
 
+const DUPLICATE_KEY: i32 = 11000;
+const NAMESPACE_NOT_FOUND: i32 = 26;
+
+/// Stored form of an [`IdempotencyRecord`], with a BSON date so that a TTL
+/// index can expire it.
+#[derive(Serialize, Deserialize)]
+pub struct IdempotencyDocument {
+    key: String,
+    request: String,
+    offer: Offer,
+    created_at: bson::DateTime,
+}
+
+impl From<&IdempotencyRecord> for IdempotencyDocument {
+    fn from(record: &IdempotencyRecord) -> Self {
+        Self {
+            key: record.key.clone(),
+            request: record.request.clone(),
+            offer: record.offer.clone(),
+            created_at: bson::DateTime::from_millis(record.created_at.timestamp_millis()),
+        }
+    }
+}
+
+impl From<IdempotencyDocument> for IdempotencyRecord {
+    fn from(document: IdempotencyDocument) -> Self {
+        Self {
+            key: document.key,
+            request: document.request,
+            offer: document.offer,
+            created_at: DateTime::from_timestamp_millis(document.created_at.timestamp_millis())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Output of the `$facet` stage of [`MongoOfferRepository::facets`].
+#[derive(Deserialize)]
+struct FacetsDocument {
+    total: Vec<TotalCount>,
+    cities: Vec<GroupCount>,
+    domains: Vec<GroupCount>,
+    salaries: Vec<BucketCount>,
+    start_months: Vec<GroupCount>,
+}
+
+#[derive(Deserialize)]
+struct TotalCount {
+    count: u64,
+}
+
+#[derive(Deserialize)]
+struct GroupCount {
+    #[serde(rename = "_id")]
+    value: String,
+    count: u64,
+}
+
+impl From<GroupCount> for FacetCount {
+    fn from(group: GroupCount) -> Self {
+        Self {
+            value: group.value,
+            count: group.count,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BucketCount {
+    /// Lower bound of the bucket.
+    #[serde(rename = "_id")]
+    min: f64,
+    count: u64,
+}
+
+/// Offer writes run in a transaction together with their outbox messages and
+/// history entries, which requires MongoDB to run as a replica set (a single
+/// node is enough).
 pub struct MongoOfferRepository {
     collection: Collection<Offer>,
+    outbox: Collection<OutboxMessage>,
+    history: Collection<HistoryEntry>,
+    idempotency: Collection<IdempotencyDocument>,
+}
+
+fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+    match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(error)) => error.code == DUPLICATE_KEY,
+        ErrorKind::InsertMany(error) => error
+            .write_errors
+            .iter()
+            .flatten()
+            .any(|error| error.code == DUPLICATE_KEY),
+        ErrorKind::Command(error) => error.code == DUPLICATE_KEY,
+        _ => false,
+    }
+}
+
+/// A write that kept losing to concurrent transactions until the driver gave up.
+fn is_write_conflict(error: &mongodb::error::Error) -> bool {
+    error.contains_label(TRANSIENT_TRANSACTION_ERROR)
+}
+
+fn is_missing_collection(error: &mongodb::error::Error) -> bool {
+    matches!(*error.kind, ErrorKind::Command(ref error) if error.code == NAMESPACE_NOT_FOUND)
+}
+
+/// Case-insensitive comparison, as [`Offer::duplicates`] does for titles and cities.
+fn case_insensitive() -> Collation {
+    Collation::builder()
+        .locale("en")
+        .strength(CollationStrength::Secondary)
+        .build()
 }
 
 impl MongoOfferRepository {
-    pub fn new(collection: Collection<Offer>) -> Self {
-        Self { collection }
+    pub fn new(
+        collection: Collection<Offer>,
+        outbox: Collection<OutboxMessage>,
+        history: Collection<HistoryEntry>,
+        idempotency: Collection<IdempotencyDocument>,
+    ) -> Self {
+        Self {
+            collection,
+            outbox,
+            history,
+            idempotency,
+        }
+    }
+
+    /// Offers stored before duplicates were rejected may break the unique
+    /// indexes; those are then left out with a warning until the duplicates
+    /// are removed.
+    pub async fn ensure_indexes(&self) -> Result<(), OfferError> {
+        let unique = [
+            IndexModel::builder()
+                .keys(doc! { "link": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .partial_filter_expression(doc! { "deleted_at": null })
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "title": 1, "city": 1, "company_id": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .collation(case_insensitive())
+                        .partial_filter_expression(
+                            doc! { "deleted_at": null, "company_id": { "$type": "string" } },
+                        )
+                        .build(),
+                )
+                .build(),
+        ];
+        // Earlier versions enforced both indexes on deleted offers and on
+        // offers of no known company too.
+        let existing: Vec<IndexModel> = match self.collection.list_indexes().await {
+            Ok(cursor) => cursor
+                .try_collect()
+                .await
+                .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?,
+            // The collection only exists once a first offer is stored.
+            Err(e) if is_missing_collection(&e) => Vec::new(),
+            Err(e) => return Err(OfferError::DatabaseError(e.to_string())),
+        };
+        for index in &unique {
+            let outdated = existing.iter().find(|stored| {
+                stored.keys == index.keys
+                    && stored
+                        .options
+                        .as_ref()
+                        .is_none_or(|options| options.partial_filter_expression.is_none())
+            });
+            if let Some(name) = outdated
+                .and_then(|stored| stored.options.as_ref())
+                .and_then(|options| options.name.as_deref())
+            {
+                self.collection
+                    .drop_index(name)
+                    .await
+                    .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+            }
+        }
+        for index in unique {
+            let keys = index.keys.clone();
+            if let Err(e) = self.collection.create_index(index).await {
+                if !is_duplicate_key(&e) {
+                    return Err(OfferError::DatabaseError(e.to_string()));
+                }
+                tracing::warn!(
+                    "Stored offers have duplicates, not enforcing {}: {}",
+                    keys,
+                    e
+                );
+            }
+        }
+        self.idempotency
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "key": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        self.idempotency
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "created_at": 1 })
+                    .options(
+                        IndexOptions::builder()
+                            .expire_after(IDEMPOTENCY_KEY_TTL.to_std().unwrap_or_default())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        self.collection
+            .create_index(IndexModel::builder().keys(doc! { "company_id": 1 }).build())
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        self.collection
+            .create_index(IndexModel::builder().keys(doc! { "skills": 1 }).build())
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        self.collection
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "salary_monthly_eur": 1 })
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        self.collection
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "location": "2dsphere" })
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        // Only scheduled offers have one, which the publication scheduler polls for.
+        self.collection
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "publish_at": 1 })
+                    .options(IndexOptions::builder().sparse(true).build())
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        // Without a language, words match as typed in French and English offers alike.
+        self.collection
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "title": "text", "description": "text" })
+                    .options(
+                        IndexOptions::builder()
+                            .weights(doc! { "title": 3, "description": 1 })
+                            .default_language("none".to_string())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        self.history
+            .create_index(IndexModel::builder().keys(doc! { "offer_id": 1 }).build())
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Runs `body` in a transaction. The driver starts it over when it loses
+    /// a write conflict to a concurrent one and retries commits whose outcome
+    /// is unknown, so callers only see conflicts that outlast its retries.
+    async fn transaction<C, R, F>(&self, context: C, body: F) -> mongodb::error::Result<R>
+    where
+        F: for<'b> FnMut(
+            &'b mut ClientSession,
+            &'b mut C,
+        ) -> BoxFuture<'b, mongodb::error::Result<R>>,
+    {
+        let mut session = self.collection.client().start_session().await?;
+        session.start_transaction().and_run(context, body).await
+    }
+
+    /// Stores the events in the outbox and the history entries, within the
+    /// transaction that made the changes.
+    async fn record(
+        &self,
+        session: &mut ClientSession,
+        log: &ChangeLog,
+    ) -> mongodb::error::Result<()> {
+        if !log.events.is_empty() {
+            self.outbox
+                .insert_many(log.events.iter().map(OutboxMessage::new))
+                .session(&mut *session)
+                .await?;
+        }
+        if !log.history.is_empty() {
+            self.history
+                .insert_many(&log.history)
+                .session(&mut *session)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Explains why `offer` could not replace the stored one: it changed
+    /// since it was read, or it is gone.
+    async fn stale_write(&self, offer: &Offer) -> OfferError {
+        match self.get_by_id(&offer.id).await {
+            Ok(Some(current)) => OfferError::VersionMismatch {
+                current: current.version,
+            },
+            Ok(None) => OfferError::NotFound,
+            Err(e) => e,
+        }
+    }
+
+    /// Gives a status to offers stored before the lifecycle existed, based on
+    /// their legacy `available` flag.
+    pub async fn backfill_status(&self) -> Result<(), OfferError> {
+        for (available, status) in [(true, OfferStatus::Published), (false, OfferStatus::Filled)] {
+            self.collection
+                .update_many(
+                    doc! { "status": { "$exists": false }, "available": available },
+                    doc! { "$set": { "status": status.as_str() } },
+                )
+                .await
+                .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Starts offers stored before versioning at version 1.
+    pub async fn backfill_versions(&self) -> Result<(), OfferError> {
+        self.collection
+            .update_many(
+                doc! { "version": { "$exists": false } },
+                doc! { "$set": { "version": 1_i64 } },
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Gives offers stored before capacity tracking a single position, already
+    /// taken when the offer was filled.
+    pub async fn backfill_capacity(&self) -> Result<(), OfferError> {
+        self.collection
+            .update_many(
+                doc! { "positions": { "$exists": false } },
+                vec![doc! { "$set": {
+                    "positions": 1,
+                    "remaining": {
+                        "$cond": [{ "$eq": ["$status", OfferStatus::Filled.as_str()] }, 0, 1]
+                    },
+                } }],
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Treats salaries stored without a currency as monthly euros, then
+    /// recomputes every monthly-EUR salary from the current exchange rates.
+    /// Offers in a currency the table no longer knows keep their last
+    /// monthly-EUR salary, or get zero if they never had one.
+    pub async fn normalize_salaries(&self, rates: &ExchangeRates) -> Result<(), OfferError> {
+        self.collection
+            .update_many(
+                doc! { "currency": { "$exists": false } },
+                doc! { "$set": {
+                    "currency": BASE_CURRENCY,
+                    "salary_period": SalaryPeriod::Monthly.as_str(),
+                } },
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        self.collection
+            .update_many(
+                doc! { "salary_period": { "$exists": false } },
+                doc! { "$set": { "salary_period": SalaryPeriod::Monthly.as_str() } },
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        for (currency, rate) in rates.currencies() {
+            for period in SalaryPeriod::ALL {
+                self.collection
+                    .update_many(
+                        doc! { "currency": currency, "salary_period": period.as_str() },
+                        vec![doc! { "$set": {
+                            "salary_monthly_eur": { "$multiply": ["$salary", rate * period.per_month()] },
+                        } }],
+                    )
+                    .await
+                    .map_err(|e: mongodb::error::Error| {
+                        OfferError::DatabaseError(e.to_string())
+                    })?;
+            }
+        }
+        let known: Vec<&str> = rates.currencies().map(|(currency, _)| currency).collect();
+        let unknown = self
+            .collection
+            .update_many(
+                doc! { "currency": { "$nin": &known }, "salary_monthly_eur": { "$exists": false } },
+                doc! { "$set": { "salary_monthly_eur": 0.0 } },
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        if unknown.modified_count > 0 {
+            tracing::warn!(
+                "{} offers are paid in currencies missing from the exchange rates",
+                unknown.modified_count
+            );
+        }
+        Ok(())
+    }
+
+    /// Locates offers stored without coordinates whose city is in the bundled
+    /// table, whatever the case it was typed in.
+    pub async fn backfill_locations(&self) -> Result<(), OfferError> {
+        for (name, point) in known_cities() {
+            self.collection
+                .update_many(
+                    doc! { "location": { "$exists": false }, "city": name },
+                    doc! { "$set": { "location": geo_json(point.lon, point.lat) } },
+                )
+                .collation(case_insensitive())
+                .await
+                .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Nearest offers first unless another sort is asked for, each with its
+    /// distance from the center.
+    async fn find_near_page(
+        &self,
+        query: &OfferQuery,
+        near: &GeoNear,
+        page: &PageRequest,
+    ) -> Result<OfferPage, OfferError> {
+        let total = self
+            .collection
+            .count_documents(query_filter(query))
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+
+        let sort = if page.sort.is_some() {
+            sort_document(page)
+        } else {
+            doc! { "distance_km": 1, "_id": -1 }
+        };
+        let pipeline = vec![
+            doc! { "$geoNear": {
+                "near": geo_json(near.center.lon, near.center.lat),
+                "distanceField": "distance_km",
+                "distanceMultiplier": 0.001,
+                "maxDistance": near.radius_km * 1000.0,
+                "query": attribute_filter(query),
+                "key": "location",
+                "spherical": true,
+            } },
+            doc! { "$sort": sort },
+            doc! { "$skip": page.offset as i64 },
+            doc! { "$limit": page.limit as i64 },
+        ];
+        let cursor = self
+            .collection
+            .aggregate(pipeline)
+            .with_type::<Offer>()
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        let offers = cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+
+        Ok(OfferPage {
+            offers,
+            total,
+            offset: page.offset,
+            limit: page.limit,
+        })
+    }
+
+    /// Turns a write that broke a unique index into [`OfferError::Duplicate`],
+    /// once its transaction has been aborted.
+    async fn write_error(&self, offers: &[Offer], error: mongodb::error::Error) -> OfferError {
+        if !is_duplicate_key(&error) {
+            return OfferError::DatabaseError(error.to_string());
+        }
+        for offer in offers {
+            match self.find_duplicate(offer).await {
+                Ok(Some(existing)) => {
+                    return OfferError::Duplicate {
+                        existing_id: existing.id,
+                    };
+                }
+                Ok(None) => {}
+                Err(e) => return e,
+            }
+        }
+        match offers
+            .iter()
+            .enumerate()
+            .find_map(|(index, offer)| offers[..index].iter().find(|other| other.duplicates(offer)))
+        {
+            Some(earlier) => OfferError::Duplicate {
+                existing_id: earlier.id.clone(),
+            },
+            None => OfferError::DatabaseError(error.to_string()),
+        }
+    }
+
+    async fn find_page(
+        &self,
+        filter: Document,
+        sort: Document,
+        page: &PageRequest,
+    ) -> Result<OfferPage, OfferError> {
+        let total = self
+            .collection
+            .count_documents(filter.clone())
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+
+        let cursor = self
+            .collection
+            .find(filter)
+            .sort(sort)
+            .skip(page.offset)
+            .limit(page.limit as i64)
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        let offers = cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+
+        Ok(OfferPage {
+            offers,
+            total,
+            offset: page.offset,
+            limit: page.limit,
+        })
     }
 }
 
+fn geo_json(lon: f64, lat: f64) -> Document {
+    doc! { "type": "Point", "coordinates": [lon, lat] }
+}
+
+fn query_filter(query: &OfferQuery) -> Document {
+    let mut filter = attribute_filter(query);
+    if let Some(near) = &query.near {
+        let center = Bson::Array(vec![near.center.lon.into(), near.center.lat.into()]);
+        filter.insert(
+            "location",
+            doc! { "$geoWithin": { "$centerSphere": [center, near.radius_km / EARTH_RADIUS_KM] } },
+        );
+    }
+    filter
+}
+
+/// Every criterion but the radius, which `$geoNear` applies on its own.
+fn attribute_filter(query: &OfferQuery) -> Document {
+    let mut filter = doc! { "deleted_at": null };
+    if !query.include_scheduled {
+        filter.insert("publish_at", Bson::Null);
+    }
+
+    if let Some(city) = &query.city {
+        filter.insert("city", city);
+    }
+    if let Some(company_id) = &query.company_id {
+        filter.insert("company_id", company_id);
+    }
+    if let Some(domain) = &query.domain {
+        filter.insert("domain", domain);
+    }
+    if let Some(text) = &query.text {
+        filter.insert("$text", doc! { "$search": text });
+    }
+    if !query.skills.is_empty() {
+        let operator = match query.skill_match {
+            SkillMatch::Any => "$in",
+            SkillMatch::All => "$all",
+        };
+        filter.insert("skills", doc! { operator: &query.skills });
+    }
+    if let Some(status) = query.status {
+        filter.insert("status", status.as_str());
+    }
+    if let Some(available) = query.available {
+        filter.insert("available", available);
+    }
+
+    let mut salary = Document::new();
+    if let Some(min) = query.min_salary {
+        salary.insert("$gte", min);
+    }
+    if let Some(max) = query.max_salary {
+        salary.insert("$lte", max);
+    }
+    if !salary.is_empty() {
+        filter.insert("salary_monthly_eur", salary);
+    }
+
+    // Dates are stored as ISO-8601 strings, so lexical comparison matches chronological order.
+    if let Some(start_from) = query.start_from {
+        filter.insert("start_date", doc! { "$gte": start_from.to_string() });
+    }
+    if let Some(end_until) = query.end_until {
+        filter.insert("end_date", doc! { "$lte": end_until.to_string() });
+    }
+
+    filter
+}
+
+fn sort_document(page: &PageRequest) -> Document {
+    let direction = match page.order {
+        SortOrder::Asc => 1,
+        SortOrder::Desc => -1,
+    };
+    let mut sort = Document::new();
+    if let Some(field) = page.sort {
+        sort.insert(field.field(), direction);
+    }
+    // ObjectIds grow with insertion time: newest first by default, and a unique
+    // tie-break so that consecutive pages never overlap.
+    sort.insert("_id", -1);
+    sort
+}
+
 impl OfferRepository for MongoOfferRepository {
-    async fn create(&self, offer: &Offer) -> Result<Offer, OfferError> {
-        self.collection
-            .insert_one(offer)
+    async fn create(&self, offer: &Offer, log: &ChangeLog) -> Result<Offer, OfferError> {
+        let created = self
+            .transaction(
+                (self, offer, log),
+                |session, &mut (repository, offer, log)| {
+                    async move {
+                        repository
+                            .collection
+                            .insert_one(offer)
+                            .session(&mut *session)
+                            .await?;
+                        repository.record(session, log).await
+                    }
+                    .boxed()
+                },
+            )
+            .await;
+        match created {
+            Ok(()) => Ok(offer.clone()),
+            Err(e) => Err(self.write_error(std::slice::from_ref(offer), e).await),
+        }
+    }
+
+    async fn create_idempotent(
+        &self,
+        offer: &Offer,
+        log: &ChangeLog,
+        record: &IdempotencyRecord,
+    ) -> Result<Offer, OfferError> {
+        let created = self
+            .transaction(
+                (self, offer, log, record),
+                |session, &mut (repository, offer, log, record)| {
+                    async move {
+                        repository
+                            .idempotency
+                            .insert_one(IdempotencyDocument::from(record))
+                            .session(&mut *session)
+                            .await
+                            .map_err(|e: mongodb::error::Error| {
+                                if is_duplicate_key(&e) {
+                                    mongodb::error::Error::custom(OfferError::IdempotencyKeyReused)
+                                } else {
+                                    e
+                                }
+                            })?;
+                        repository
+                            .collection
+                            .insert_one(offer)
+                            .session(&mut *session)
+                            .await?;
+                        repository.record(session, log).await
+                    }
+                    .boxed()
+                },
+            )
+            .await;
+        match created {
+            Ok(()) => Ok(offer.clone()),
+            Err(e) if e.get_custom::<OfferError>().is_some() => {
+                Err(OfferError::IdempotencyKeyReused)
+            }
+            Err(e) => Err(self.write_error(std::slice::from_ref(offer), e).await),
+        }
+    }
+
+    async fn find_idempotency_record(
+        &self,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>, OfferError> {
+        // The TTL monitor only runs every minute or so.
+        let now = Utc::now();
+        let record = self
+            .idempotency
+            .find_one(doc! { "key": key })
             .await
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
-        Ok(offer.clone())
+        Ok(record
+            .map(IdempotencyRecord::from)
+            .filter(|record| !record.is_expired(now)))
+    }
+
+    async fn create_many(&self, offers: &[Offer], log: &ChangeLog) -> Result<(), OfferError> {
+        if offers.is_empty() {
+            return Ok(());
+        }
+        let created = self
+            .transaction(
+                (self, offers, log),
+                |session, &mut (repository, offers, log)| {
+                    async move {
+                        repository
+                            .collection
+                            .insert_many(offers)
+                            .session(&mut *session)
+                            .await?;
+                        repository.record(session, log).await
+                    }
+                    .boxed()
+                },
+            )
+            .await;
+        match created {
+            Ok(()) => Ok(()),
+            Err(e) => Err(self.write_error(offers, e).await),
+        }
     }
 
     async fn get_by_id(&self, id: &str) -> Result<Option<Offer>, OfferError> {
@@ -30,22 +779,149 @@ impl OfferRepository for MongoOfferRepository {
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))
     }
 
-    async fn list_by_domain(&self, domain: &str) -> Result<Vec<Offer>, OfferError> {
-        let cursor = self
+    async fn find_duplicate(&self, offer: &Offer) -> Result<Option<Offer>, OfferError> {
+        if offer.is_deleted() {
+            return Ok(None);
+        }
+        let same_link = self
             .collection
-            .find(doc! { "domain": domain })
+            .find_one(doc! { "id": { "$ne": &offer.id }, "link": &offer.link, "deleted_at": null })
             .await
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
-        cursor
-            .try_collect()
+        if same_link.is_some() {
+            return Ok(same_link);
+        }
+        let Some(company_id) = offer.company_id.as_deref() else {
+            return Ok(None);
+        };
+        self.collection
+            .find_one(doc! {
+                "id": { "$ne": &offer.id },
+                "title": &offer.title,
+                "city": &offer.city,
+                "company_id": company_id,
+                "deleted_at": null,
+            })
+            .collation(case_insensitive())
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))
+    }
+
+    async fn search(
+        &self,
+        query: &OfferQuery,
+        page: &PageRequest,
+    ) -> Result<OfferPage, OfferError> {
+        match &query.near {
+            Some(near) => self.find_near_page(query, near, page).await,
+            None => {
+                let sort = match (&query.text, page.sort) {
+                    (Some(_), None) => doc! { "score": { "$meta": "textScore" }, "_id": -1 },
+                    _ => sort_document(page),
+                };
+                self.find_page(query_filter(query), sort, page).await
+            }
+        }
+    }
+
+    async fn facets(&self, query: &OfferQuery) -> Result<OfferFacets, OfferError> {
+        let count_by =
+            |field: &str| vec![doc! { "$group": { "_id": field, "count": { "$sum": 1 } } }];
+        let last_bound = SALARY_BUCKET_BOUNDS[SALARY_BUCKET_BOUNDS.len() - 1];
+        let pipeline = vec![
+            doc! { "$match": query_filter(query) },
+            doc! { "$facet": {
+                "total": [{ "$count": "count" }],
+                "cities": count_by("$city"),
+                "domains": count_by("$domain"),
+                "salaries": [{ "$bucket": {
+                    "groupBy": "$salary_monthly_eur",
+                    "boundaries": SALARY_BUCKET_BOUNDS.to_vec(),
+                    // Values past the last bound, which `$bucket` excludes.
+                    "default": last_bound,
+                } }],
+                "start_months": [
+                    // Dates are stored as `YYYY-MM-DD` strings.
+                    { "$group": {
+                        "_id": { "$substrCP": ["$start_date", 0, 7] },
+                        "count": { "$sum": 1 },
+                    } },
+                    { "$sort": { "_id": 1 } },
+                ],
+            } },
+        ];
+        let mut cursor = self
+            .collection
+            .aggregate(pipeline)
+            .with_type::<FacetsDocument>()
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        let Some(facets) = cursor
+            .try_next()
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?
+        else {
+            return Ok(OfferFacets::default());
+        };
+
+        let mut salaries = [0; SALARY_BUCKET_BOUNDS.len()];
+        for bucket in facets.salaries {
+            salaries[salary_bucket(bucket.min)] += bucket.count;
+        }
+        Ok(OfferFacets {
+            total: facets.total.first().map_or(0, |total| total.count),
+            cities: by_count(facets.cities.into_iter().map(FacetCount::from).collect()),
+            domains: by_count(facets.domains.into_iter().map(FacetCount::from).collect()),
+            salaries: salary_buckets(salaries),
+            start_months: facets
+                .start_months
+                .into_iter()
+                .map(FacetCount::from)
+                .collect(),
+        })
+    }
+
+    async fn stream(
+        &self,
+        query: &OfferQuery,
+    ) -> Result<BoxStream<'static, Result<Offer, OfferError>>, OfferError> {
+        let cursor = self
+            .collection
+            .find(query_filter(query))
+            .sort(doc! { "_id": 1 })
             .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        Ok(cursor
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))
+            .boxed())
     }
 
-    async fn list_by_city(&self, city: &str) -> Result<Vec<Offer>, OfferError> {
+    async fn list_all(&self, page: &PageRequest) -> Result<OfferPage, OfferError> {
+        self.find_page(
+            doc! {
+                "status": OfferStatus::Published.as_str(),
+                "deleted_at": null,
+                "publish_at": null,
+            },
+            sort_document(page),
+            page,
+        )
+        .await
+    }
+
+    async fn list_past_due(
+        &self,
+        reference: ExpiryReference,
+        today: NaiveDate,
+    ) -> Result<Vec<Offer>, OfferError> {
         let cursor = self
             .collection
-            .find(doc! { "city": city })
+            .find(doc! {
+                "status": OfferStatus::Published.as_str(),
+                "deleted_at": null,
+                "publish_at": null,
+                reference.field(): { "$lt": today.to_string() },
+            })
             .await
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
         cursor
@@ -54,31 +930,123 @@ impl OfferRepository for MongoOfferRepository {
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))
     }
 
-    async fn update(&self, offer: &Offer) -> Result<Offer, OfferError> {
-        let result = self
+    async fn list_due_for_publication(&self, now: DateTime<Utc>) -> Result<Vec<Offer>, OfferError> {
+        // `publish_at` is stored with whole seconds, see `Offer::schedule`.
+        let now = now.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let cursor = self
             .collection
-            .replace_one(doc! { "id": &offer.id }, offer)
+            .find(doc! { "publish_at": { "$lte": now }, "deleted_at": null })
             .await
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))
+    }
+
+    async fn reserve(&self, id: &str, actor: Option<&str>) -> Result<Option<Offer>, OfferError> {
+        let reserved = self
+            .transaction((self, id, actor), |session, &mut (repository, id, actor)| {
+                async move {
+                    let filled = OfferStatus::Filled.as_str();
+                    let before = repository
+                        .collection
+                        .find_one_and_update(
+                            doc! {
+                                "id": id,
+                                "status": OfferStatus::Published.as_str(),
+                                "remaining": { "$gt": 0 },
+                                "deleted_at": null,
+                                "publish_at": null,
+                            },
+                            vec![
+                                doc! { "$set": {
+                                    "remaining": { "$subtract": ["$remaining", 1] },
+                                    "version": { "$add": ["$version", 1] },
+                                } },
+                                doc! { "$set": {
+                                    "status": { "$cond": [{ "$eq": ["$remaining", 0] }, filled, "$status"] },
+                                    "available": { "$gt": ["$remaining", 0] },
+                                } },
+                            ],
+                        )
+                        .return_document(ReturnDocument::Before)
+                        .session(&mut *session)
+                        .await?;
+
+                    let Some(before) = before else {
+                        return Ok(None);
+                    };
+                    let mut after = before.clone();
+                    after.take_seat();
+                    let update = OfferUpdate::new(&before, after);
+                    repository
+                        .record(session, &ChangeLog::updated(&update, actor))
+                        .await?;
+                    Ok(Some(update.offer))
+                }
+                .boxed()
+            })
+            .await;
+        match reserved {
+            Ok(offer) => Ok(offer),
+            // Concurrent reservations kept taking the seats first.
+            Err(e) if is_write_conflict(&e) => Ok(None),
+            Err(e) => Err(OfferError::DatabaseError(e.to_string())),
+        }
+    }
 
-        if result.matched_count == 0 {
-            return Err(OfferError::NotFound);
+    async fn update(&self, offer: &Offer, log: &ChangeLog) -> Result<Offer, OfferError> {
+        let replaced = self
+            .transaction(
+                (self, offer, log),
+                |session, &mut (repository, offer, log)| {
+                    async move {
+                        let read_version = offer.version.saturating_sub(1) as i64;
+                        let result = repository
+                            .collection
+                            .replace_one(doc! { "id": &offer.id, "version": read_version }, offer)
+                            .session(&mut *session)
+                            .await?;
+                        if result.matched_count == 0 {
+                            return Ok(false);
+                        }
+                        repository.record(session, log).await?;
+                        Ok(true)
+                    }
+                    .boxed()
+                },
+            )
+            .await;
+        match replaced {
+            Ok(true) => Ok(offer.clone()),
+            Ok(false) => Err(self.stale_write(offer).await),
+            Err(e) if is_write_conflict(&e) => Err(self.stale_write(offer).await),
+            Err(e) => Err(self.write_error(std::slice::from_ref(offer), e).await),
         }
+    }
 
-        Ok(offer.clone())
+    async fn history(&self, offer_id: &str) -> Result<Vec<HistoryEntry>, OfferError> {
+        let cursor = self
+            .history
+            .find(doc! { "offer_id": offer_id })
+            .sort(doc! { "_id": 1 })
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))
     }
 
-    async fn delete(&self, id: &str) -> Result<(), OfferError> {
+    async fn purge_deleted(&self, before: DateTime<Utc>) -> Result<u64, OfferError> {
+        // `deleted_at` is stored with whole seconds, see `Offer::mark_deleted`.
+        let before = before.to_rfc3339_opts(SecondsFormat::Secs, true);
         let result = self
             .collection
-            .delete_one(doc! { "id": id })
+            .delete_many(doc! { "deleted_at": { "$lt": before } })
             .await
             .map_err(|e: mongodb::error::Error| OfferError::DatabaseError(e.to_string()))?;
-
-        if result.deleted_count == 0 {
-            return Err(OfferError::NotFound);
-        }
-
-        Ok(())
+        Ok(result.deleted_count)
     }
 }
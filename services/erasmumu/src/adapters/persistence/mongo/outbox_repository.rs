@@ -0,0 +1,147 @@
+use crate::domain::outbox::OutboxMessage;
+use crate::domain::ports::outbox_repository::{OutboxError, OutboxRepository};
+use futures_util::TryStreamExt;
+use mongodb::bson::{Document, doc};
+use mongodb::error::ErrorKind;
+use mongodb::options::IndexOptions;
+use mongodb::{Collection, IndexModel};
+use std::time::Duration;
+
+const NAMESPACE_NOT_FOUND: i32 = 26;
+
+/// How long messages are kept around for debugging, once both relayed and
+/// fanned out to webhooks, before MongoDB drops them.
+const DONE_RETENTION: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Stamps `field` and, when `other` is stamped already, `done_at` as well,
+/// which is what the retention index expires messages on.
+fn mark(field: &str, other: &str) -> Vec<Document> {
+    vec![doc! {
+        "$set": {
+            field: "$$NOW",
+            "done_at": {
+                "$cond": [
+                    { "$eq": [{ "$type": format!("${}", other) }, "missing"] },
+                    "$$REMOVE",
+                    "$$NOW",
+                ]
+            },
+        }
+    }]
+}
+
+fn is_missing_collection(error: &mongodb::error::Error) -> bool {
+    matches!(*error.kind, ErrorKind::Command(ref error) if error.code == NAMESPACE_NOT_FOUND)
+}
+
+pub struct MongoOutboxRepository {
+    collection: Collection<OutboxMessage>,
+}
+
+impl MongoOutboxRepository {
+    pub fn new(collection: Collection<OutboxMessage>) -> Self {
+        Self { collection }
+    }
+
+    pub async fn ensure_indexes(&self) -> Result<(), OutboxError> {
+        // Earlier versions expired messages once relayed, even those still
+        // waiting to be handed to webhooks.
+        let indexes: Vec<IndexModel> = match self.collection.list_indexes().await {
+            Ok(cursor) => cursor
+                .try_collect()
+                .await
+                .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?,
+            // The collection only exists once a first message is recorded.
+            Err(e) if is_missing_collection(&e) => Vec::new(),
+            Err(e) => return Err(OutboxError::DatabaseError(e.to_string())),
+        };
+        if indexes.iter().any(|index| {
+            index.keys == doc! { "sent_at": 1 }
+                && index
+                    .options
+                    .as_ref()
+                    .is_some_and(|options| options.expire_after.is_some())
+        }) {
+            self.collection
+                .drop_index("sent_at_1")
+                .await
+                .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?;
+        }
+
+        self.collection
+            .create_indexes([
+                IndexModel::builder().keys(doc! { "id": 1 }).build(),
+                IndexModel::builder().keys(doc! { "sent_at": 1 }).build(),
+                IndexModel::builder()
+                    .keys(doc! { "fanned_out_at": 1 })
+                    .build(),
+                IndexModel::builder()
+                    .keys(doc! { "done_at": 1 })
+                    .options(IndexOptions::builder().expire_after(DONE_RETENTION).build())
+                    .build(),
+            ])
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl OutboxRepository for MongoOutboxRepository {
+    async fn pending(&self, limit: i64) -> Result<Vec<OutboxMessage>, OutboxError> {
+        let cursor = self
+            .collection
+            .find(doc! { "sent_at": { "$exists": false } })
+            .sort(doc! { "_id": 1 })
+            .limit(limit)
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))
+    }
+
+    async fn mark_sent(&self, id: &str) -> Result<(), OutboxError> {
+        self.collection
+            .update_one(doc! { "id": id }, mark("sent_at", "fanned_out_at"))
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn pending_fan_out(&self, limit: i64) -> Result<Vec<OutboxMessage>, OutboxError> {
+        let cursor = self
+            .collection
+            .find(doc! { "fanned_out_at": { "$exists": false } })
+            .sort(doc! { "_id": 1 })
+            .limit(limit)
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))
+    }
+
+    async fn mark_fanned_out(&self, id: &str) -> Result<(), OutboxError> {
+        self.collection
+            .update_one(doc! { "id": id }, mark("fanned_out_at", "sent_at"))
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn record_failure(&self, id: &str, error: &str) -> Result<(), OutboxError> {
+        self.collection
+            .update_one(
+                doc! { "id": id },
+                doc! {
+                    "$inc": { "attempts": 1 },
+                    "$set": { "last_error": error },
+                },
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| OutboxError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
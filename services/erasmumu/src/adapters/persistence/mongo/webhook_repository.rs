@@ -0,0 +1,184 @@
+use crate::domain::ports::webhook_repository::{WebhookError, WebhookRepository};
+use crate::domain::webhook::{DeliveryStatus, WebhookDelivery, WebhookSubscription};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::options::IndexOptions;
+use mongodb::{Collection, IndexModel};
+
+pub struct MongoWebhookRepository {
+    subscriptions: Collection<WebhookSubscription>,
+    deliveries: Collection<WebhookDelivery>,
+}
+
+impl MongoWebhookRepository {
+    pub fn new(
+        subscriptions: Collection<WebhookSubscription>,
+        deliveries: Collection<WebhookDelivery>,
+    ) -> Self {
+        Self {
+            subscriptions,
+            deliveries,
+        }
+    }
+
+    pub async fn ensure_indexes(&self) -> Result<(), WebhookError> {
+        self.subscriptions
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "id": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        // Only pending deliveries have a next attempt.
+        self.deliveries
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "next_attempt_at": 1 })
+                    .options(IndexOptions::builder().sparse(true).build())
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        self.deliveries
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "subscription_id": 1, "_id": -1 })
+                    .build(),
+            )
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl WebhookRepository for MongoWebhookRepository {
+    async fn create_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        self.subscriptions
+            .insert_one(subscription)
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        Ok(subscription.clone())
+    }
+
+    async fn get_subscription(
+        &self,
+        id: &str,
+    ) -> Result<Option<WebhookSubscription>, WebhookError> {
+        self.subscriptions
+            .find_one(doc! { "id": id })
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))
+    }
+
+    async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, WebhookError> {
+        let cursor = self
+            .subscriptions
+            .find(doc! {})
+            .sort(doc! { "_id": 1 })
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))
+    }
+
+    async fn update_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        let result = self
+            .subscriptions
+            .replace_one(doc! { "id": &subscription.id }, subscription)
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+
+        if result.matched_count == 0 {
+            return Err(WebhookError::NotFound);
+        }
+        Ok(subscription.clone())
+    }
+
+    async fn delete_subscription(&self, id: &str) -> Result<(), WebhookError> {
+        let result = self
+            .subscriptions
+            .delete_one(doc! { "id": id })
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+
+        if result.deleted_count == 0 {
+            return Err(WebhookError::NotFound);
+        }
+        self.deliveries
+            .delete_many(doc! { "subscription_id": id })
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn enqueue(&self, deliveries: &[WebhookDelivery]) -> Result<(), WebhookError> {
+        if deliveries.is_empty() {
+            return Ok(());
+        }
+        self.deliveries
+            .insert_many(deliveries)
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        // `next_attempt_at` is stored with whole seconds, see `WebhookDelivery`.
+        let now = now.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let cursor = self
+            .deliveries
+            .find(doc! {
+                "status": DeliveryStatus::Pending.as_str(),
+                "next_attempt_at": { "$lte": now },
+            })
+            .sort(doc! { "_id": 1 })
+            .limit(limit as i64)
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))
+    }
+
+    async fn save_delivery(&self, delivery: &WebhookDelivery) -> Result<(), WebhookError> {
+        self.deliveries
+            .replace_one(doc! { "id": &delivery.id }, delivery)
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn deliveries(
+        &self,
+        subscription_id: &str,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        let cursor = self
+            .deliveries
+            .find(doc! { "subscription_id": subscription_id })
+            .sort(doc! { "_id": -1 })
+            .limit(limit as i64)
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))?;
+        cursor
+            .try_collect()
+            .await
+            .map_err(|e: mongodb::error::Error| WebhookError::DatabaseError(e.to_string()))
+    }
+}
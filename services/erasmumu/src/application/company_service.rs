@@ -0,0 +1,136 @@
+use crate::application::offer_service::{OfferService, is_web_url};
+use crate::domain::company::Company;
+use crate::domain::ports::company_repository::{CompanyError, CompanyRepository};
+use crate::domain::ports::offer_repository::{FieldError, OfferRepository};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct CreateCompanyParams {
+    pub name: String,
+    pub website: Option<String>,
+    pub description: Option<String>,
+    pub cities: Vec<String>,
+}
+
+/// An empty `website` or `description` clears it.
+pub struct UpdateCompanyParams {
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub description: Option<String>,
+    pub cities: Option<Vec<String>>,
+}
+
+fn optional_text(raw: Option<String>) -> Option<String> {
+    raw.map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Checks every field and reports all violations at once, returning the
+/// company with trimmed values and de-duplicated cities.
+fn validate_company(mut company: Company) -> Result<Company, CompanyError> {
+    let mut errors = Vec::new();
+
+    company.name = company.name.trim().to_string();
+    if company.name.is_empty() {
+        errors.push(FieldError::new("name", "must not be empty"));
+    }
+
+    if let Some(website) = &company.website
+        && !is_web_url(website)
+    {
+        errors.push(FieldError::new(
+            "website",
+            "must be an absolute http(s) URL",
+        ));
+    }
+
+    let mut cities: Vec<String> = Vec::with_capacity(company.cities.len());
+    for city in &company.cities {
+        let city = city.trim();
+        if city.is_empty() {
+            errors.push(FieldError::new("cities", "must not contain empty names"));
+        } else if !cities.iter().any(|known| known.eq_ignore_ascii_case(city)) {
+            cities.push(city.to_string());
+        }
+    }
+    company.cities = cities;
+
+    if errors.is_empty() {
+        Ok(company)
+    } else {
+        Err(CompanyError::ValidationFailed(errors))
+    }
+}
+
+pub struct CompanyService<R: OfferRepository, C: CompanyRepository> {
+    repository: Arc<C>,
+    offers: Arc<OfferService<R, C>>,
+}
+
+impl<R: OfferRepository, C: CompanyRepository> CompanyService<R, C> {
+    pub fn new(repository: Arc<C>, offers: Arc<OfferService<R, C>>) -> Self {
+        Self { repository, offers }
+    }
+
+    pub async fn create_company(
+        &self,
+        params: CreateCompanyParams,
+    ) -> Result<Company, CompanyError> {
+        let company = validate_company(Company {
+            id: Uuid::new_v4().to_string(),
+            name: params.name,
+            website: optional_text(params.website),
+            description: optional_text(params.description),
+            cities: params.cities,
+        })?;
+        self.repository.create(&company).await
+    }
+
+    pub async fn get_company(&self, id: &str) -> Result<Company, CompanyError> {
+        self.repository
+            .get_by_id(id)
+            .await?
+            .ok_or(CompanyError::NotFound)
+    }
+
+    pub async fn list_companies(&self) -> Result<Vec<Company>, CompanyError> {
+        self.repository.list().await
+    }
+
+    pub async fn update_company(
+        &self,
+        id: &str,
+        params: UpdateCompanyParams,
+    ) -> Result<Company, CompanyError> {
+        let existing = self.get_company(id).await?;
+        let company = validate_company(Company {
+            id: existing.id,
+            name: params.name.unwrap_or(existing.name),
+            website: match params.website {
+                Some(website) => optional_text(Some(website)),
+                None => existing.website,
+            },
+            description: match params.description {
+                Some(description) => optional_text(Some(description)),
+                None => existing.description,
+            },
+            cities: params.cities.unwrap_or(existing.cities),
+        })?;
+        self.repository.update(&company).await
+    }
+
+    /// Refuses to delete a company that offers still reference; deleted offers
+    /// do not count.
+    pub async fn delete_company(&self, id: &str) -> Result<(), CompanyError> {
+        self.get_company(id).await?;
+        let offers = self
+            .offers
+            .count_company_offers(id)
+            .await
+            .map_err(|e| CompanyError::DatabaseError(e.to_string()))?;
+        if offers > 0 {
+            return Err(CompanyError::InUse(offers));
+        }
+        self.repository.delete(id).await
+    }
+}
@@ -0,0 +1,27 @@
+use crate::application::offer_service::OfferService;
+use crate::domain::offer::{ExpiryReference, Offer};
+use crate::domain::ports::clock::Clock;
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::{OfferError, OfferRepository};
+use std::sync::Arc;
+
+pub struct ExpirySweeper<R: OfferRepository, C: CompanyRepository, K: Clock> {
+    service: Arc<OfferService<R, C>>,
+    clock: K,
+    reference: ExpiryReference,
+}
+
+impl<R: OfferRepository, C: CompanyRepository, K: Clock> ExpirySweeper<R, C, K> {
+    pub fn new(service: Arc<OfferService<R, C>>, clock: K, reference: ExpiryReference) -> Self {
+        Self {
+            service,
+            clock,
+            reference,
+        }
+    }
+
+    pub async fn sweep(&self) -> Result<Vec<Offer>, OfferError> {
+        let today = self.clock.now().date_naive();
+        self.service.expire_offers(self.reference, today).await
+    }
+}
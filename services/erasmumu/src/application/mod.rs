@@ -1 +1,6 @@
+pub mod company_service;
+pub mod expiry_sweeper;
 pub mod offer_service;
+pub mod publication_scheduler;
+pub mod tombstone_purger;
+pub mod webhook_service;
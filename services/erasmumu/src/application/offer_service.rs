@@ -1,15 +1,58 @@
-use crate::domain::offer::Offer;
-use crate::domain::ports::offer_repository::{OfferError, OfferRepository};
+use crate::domain::events::OfferEvent;
+use crate::domain::facets::OfferFacets;
+use crate::domain::geo::{GeoPoint, city_coordinates};
+use crate::domain::history::{ChangeLog, HistoryEntry};
+use crate::domain::idempotency::{IdempotencyRecord, MAX_IDEMPOTENCY_KEY_LEN};
+use crate::domain::offer::{
+    ExpiryReference, Offer, OfferPage, OfferQuery, OfferStatus, OfferUpdate, PageRequest,
+    Reservation,
+};
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::{FieldError, OfferError, OfferRepository};
+use crate::domain::salary::{
+    BASE_CURRENCY, ExchangeRates, SalaryPeriod, is_currency_code, normalize_currency,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::stream::BoxStream;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use url::Url;
 use uuid::Uuid;
 
+pub const MAX_PAGE_SIZE: u64 = 100;
+pub const MAX_RADIUS_KM: f64 = 1000.0;
+pub const MAX_SKILLS: usize = 20;
+pub const MAX_DESCRIPTION_LEN: usize = 5000;
+pub const MAX_SEARCH_TEXT_LEN: usize = 200;
+const IMPORT_BATCH_SIZE: usize = 500;
+/// Actor recorded in the history of offers expired by the sweep.
+const EXPIRY_ACTOR: &str = "expiry-sweep";
+/// Actor recorded in the history of offers published by the scheduler.
+const PUBLICATION_ACTOR: &str = "publication-scheduler";
+
 pub struct CreateOfferParams {
     pub title: String,
     pub link: String,
     pub city: String,
     pub domain: String,
+    pub description: Option<String>,
     pub salary: f64,
+    /// Defaults to euros.
+    pub currency: Option<String>,
+    /// Defaults to monthly.
+    pub salary_period: Option<SalaryPeriod>,
     pub start_date: String,
     pub end_date: String,
+    pub status: Option<OfferStatus>,
+    pub positions: Option<u32>,
+    pub company_id: Option<String>,
+    /// Keeps the offer hidden until then; a time already past publishes it at once.
+    pub publish_at: Option<DateTime<Utc>>,
+    pub skills: Vec<String>,
+    /// Defaults to the coordinates of `city` when both are missing.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 pub struct UpdateOfferParams {
@@ -17,81 +60,1006 @@ pub struct UpdateOfferParams {
     pub link: Option<String>,
     pub city: Option<String>,
     pub domain: Option<String>,
+    /// An empty description clears it.
+    pub description: Option<String>,
     pub salary: Option<f64>,
+    pub currency: Option<String>,
+    pub salary_period: Option<SalaryPeriod>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    pub positions: Option<u32>,
+    /// Legacy availability toggle, applied as a `published`/`filled` transition.
     pub available: Option<bool>,
+    /// An empty id detaches the offer from its company.
+    pub company_id: Option<String>,
+    /// Reschedules an offer that is not live yet, or publishes it at once
+    /// with a time already past.
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Replaces the whole list.
+    pub skills: Option<Vec<String>>,
+    /// Without them, a new `city` moves the offer to that city's coordinates.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
-pub struct OfferService<R: OfferRepository> {
-    repository: R,
+/// One row of an import, numbered from 1 as in the submitted file, holding
+/// either the parsed parameters or why the row could not be parsed.
+pub struct ImportRow {
+    pub row: usize,
+    pub params: Result<CreateOfferParams, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub received: usize,
+    pub accepted: Vec<ImportedRow>,
+    pub errors: Vec<ImportRowError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedRow {
+    pub row: usize,
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub error: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+    /// Id of the offer, stored or from an earlier row, that this row duplicates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+}
+
+impl ImportRowError {
+    fn new(row: usize, error: OfferError) -> Self {
+        let mut report = Self {
+            row,
+            error: error.to_string(),
+            fields: Vec::new(),
+            duplicate_of: None,
+        };
+        match error {
+            OfferError::ValidationFailed(fields) => {
+                report.error = "Invalid offer".to_string();
+                report.fields = fields;
+            }
+            OfferError::InvalidData(message) => report.error = message,
+            OfferError::Duplicate { existing_id } => report.duplicate_of = Some(existing_id),
+            _ => {}
+        }
+        report
+    }
+}
+
+/// An offer created by an idempotent request, `replayed` when it was created
+/// by an earlier attempt of the same request.
+pub struct CreatedOffer {
+    pub offer: Offer,
+    pub replayed: bool,
+}
+
+/// Offer fields as they will be stored, with dates that may have failed to parse.
+struct OfferFields<'a> {
+    title: &'a str,
+    link: &'a str,
+    city: &'a str,
+    domain: &'a str,
+    salary: f64,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+}
+
+fn parse_date(field: &'static str, raw: &str, errors: &mut Vec<FieldError>) -> Option<NaiveDate> {
+    match NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d") {
+        Ok(date) => Some(date),
+        Err(_) => {
+            errors.push(FieldError::new(
+                field,
+                "must be a date formatted as YYYY-MM-DD",
+            ));
+            None
+        }
+    }
+}
+
+/// Checks every field and reports all violations at once, alongside any date parsing errors.
+fn validate_fields(
+    fields: &OfferFields,
+    mut errors: Vec<FieldError>,
+) -> Result<(NaiveDate, NaiveDate), OfferError> {
+    for (name, value) in [
+        ("title", fields.title),
+        ("city", fields.city),
+        ("domain", fields.domain),
+    ] {
+        if value.trim().is_empty() {
+            errors.push(FieldError::new(name, "must not be empty"));
+        }
+    }
+
+    if !is_web_url(fields.link) {
+        errors.push(FieldError::new("link", "must be an absolute http(s) URL"));
+    }
+
+    if !fields.salary.is_finite() || fields.salary < 0.0 {
+        errors.push(FieldError::new("salary", "must be a non-negative number"));
+    }
+
+    if let (Some(start), Some(end)) = (fields.start_date, fields.end_date)
+        && end <= start
+    {
+        errors.push(FieldError::new("end_date", "must be after start_date"));
+    }
+
+    match (fields.start_date, fields.end_date) {
+        (Some(start), Some(end)) if errors.is_empty() => Ok((start, end)),
+        _ => Err(OfferError::ValidationFailed(errors)),
+    }
+}
+
+/// Explicit coordinates win; otherwise the offer is placed at its city, when
+/// the bundled table knows it.
+fn locate(
+    city: &str,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    errors: &mut Vec<FieldError>,
+) -> Option<GeoPoint> {
+    match (latitude, longitude) {
+        (Some(lat), Some(lon)) => match GeoPoint::new(lat, lon) {
+            Ok(point) => Some(point),
+            Err(message) => {
+                // Named after the coordinate it rejects, as in "latitude must be ...".
+                let field = if message.starts_with("latitude") {
+                    "latitude"
+                } else {
+                    "longitude"
+                };
+                errors.push(FieldError::new(
+                    field,
+                    message.trim_start_matches(field).trim_start(),
+                ));
+                None
+            }
+        },
+        (Some(_), None) => {
+            errors.push(FieldError::new("longitude", "must be given with latitude"));
+            None
+        }
+        (None, Some(_)) => {
+            errors.push(FieldError::new("latitude", "must be given with longitude"));
+            None
+        }
+        (None, None) => city_coordinates(city),
+    }
+}
+
+pub(crate) fn is_web_url(raw: &str) -> bool {
+    matches!(Url::parse(raw), Ok(url) if matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
+
+/// Checks the currency against the exchange-rate table and converts the
+/// salary to euros per month, `0` when it cannot be converted.
+fn normalize_salary(
+    salary: f64,
+    currency: &str,
+    period: SalaryPeriod,
+    rates: &ExchangeRates,
+    errors: &mut Vec<FieldError>,
+) -> (String, f64) {
+    let currency = normalize_currency(currency);
+    let monthly_eur = if !is_currency_code(&currency) {
+        errors.push(FieldError::new(
+            "currency",
+            "must be a three-letter currency code",
+        ));
+        None
+    } else {
+        let converted = rates.monthly_eur(salary, &currency, period);
+        if converted.is_none() {
+            errors.push(FieldError::new("currency", "has no exchange rate"));
+        }
+        converted
+    };
+    (currency, monthly_eur.unwrap_or_default())
+}
+
+/// Skills compare case-insensitively, so they are stored lowercased.
+fn normalize_skill(raw: &str) -> String {
+    raw.trim().to_lowercase()
+}
+
+/// Normalizes and de-duplicates skills, keeping their order.
+fn validate_skills(raw: Vec<String>, errors: &mut Vec<FieldError>) -> Vec<String> {
+    let mut skills: Vec<String> = Vec::with_capacity(raw.len());
+    for skill in raw.iter().map(|skill| normalize_skill(skill)) {
+        if skill.is_empty() {
+            errors.push(FieldError::new("skills", "must not contain empty names"));
+        } else if !skills.contains(&skill) {
+            skills.push(skill);
+        }
+    }
+    if skills.len() > MAX_SKILLS {
+        errors.push(FieldError::new(
+            "skills",
+            format!("must not list more than {} skills", MAX_SKILLS),
+        ));
+    }
+    skills
 }
 
-impl<R: OfferRepository> OfferService<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+/// Trims a description, an empty one meaning none.
+fn validate_description(raw: Option<String>, errors: &mut Vec<FieldError>) -> Option<String> {
+    let description = raw
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())?;
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        errors.push(FieldError::new(
+            "description",
+            format!("must not be longer than {} characters", MAX_DESCRIPTION_LEN),
+        ));
     }
+    Some(description)
+}
+
+/// When the offer should go live, `None` when it already can.
+fn validate_publish_at(
+    publish_at: Option<DateTime<Utc>>,
+    end_date: Option<NaiveDate>,
+    now: DateTime<Utc>,
+    errors: &mut Vec<FieldError>,
+) -> Option<DateTime<Utc>> {
+    let publish_at = publish_at.filter(|at| *at > now)?;
+    if end_date.is_some_and(|end| publish_at.date_naive() > end) {
+        errors.push(FieldError::new("publish_at", "must not be after end_date"));
+    }
+    Some(publish_at)
+}
+
+/// Trims a company reference, an empty one meaning no company.
+fn company_ref(raw: Option<String>) -> Option<String> {
+    raw.map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+/// Checks the move to `to` for an offer that will have `remaining` seats left.
+fn ensure_transition(from: OfferStatus, to: OfferStatus, remaining: u32) -> Result<(), OfferError> {
+    if from != to && !from.can_transition_to(to) {
+        Err(OfferError::InvalidTransition { from, to })
+    } else if from != to && to == OfferStatus::Published && remaining == 0 {
+        Err(OfferError::NoSeatsLeft)
+    } else {
+        Ok(())
+    }
+}
+
+/// Lets a background job skip an offer that changed under it, e.g. edited or
+/// deleted since it was listed, and only stop on database failures.
+fn skip_conflict(actor: &str, id: &str, err: OfferError) -> Result<(), OfferError> {
+    match err {
+        OfferError::DatabaseError(_) => Err(err),
+        err => {
+            tracing::warn!("{} skipped offer {}: {}", actor, id, err);
+            Ok(())
+        }
+    }
+}
+
+/// Rejects contradictory filters and, unless another state is explicitly
+/// requested, restricts the query to published offers.
+fn prepare_query(query: &mut OfferQuery) -> Result<(), OfferError> {
+    if let (Some(min), Some(max)) = (query.min_salary, query.max_salary)
+        && min > max
+    {
+        return Err(OfferError::InvalidData(
+            "min_salary must not be greater than max_salary".to_string(),
+        ));
+    }
+
+    if let Some(near) = &query.near
+        && !(near.radius_km > 0.0 && near.radius_km <= MAX_RADIUS_KM)
+    {
+        return Err(OfferError::InvalidData(format!(
+            "radius_km must be greater than 0 and at most {}",
+            MAX_RADIUS_KM
+        )));
+    }
+
+    query.text = query
+        .text
+        .take()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    if let Some(text) = &query.text {
+        if text.chars().count() > MAX_SEARCH_TEXT_LEN {
+            return Err(OfferError::InvalidData(format!(
+                "q must not be longer than {} characters",
+                MAX_SEARCH_TEXT_LEN
+            )));
+        }
+        // MongoDB cannot combine a text search with `$geoNear`.
+        if query.near.is_some() {
+            return Err(OfferError::InvalidData(
+                "q cannot be combined with near".to_string(),
+            ));
+        }
+    }
+
+    query.skills = query
+        .skills
+        .iter()
+        .map(|skill| normalize_skill(skill))
+        .filter(|skill| !skill.is_empty())
+        .collect();
+
+    if query.status.is_none() && query.available.is_none() {
+        query.status = Some(OfferStatus::Published);
+    }
+    Ok(())
+}
 
-    pub async fn create_offer(&self, params: CreateOfferParams) -> Result<Offer, OfferError> {
-        let offer = Offer {
-            id: Uuid::new_v4().to_string(),
-            title: params.title,
-            link: params.link,
-            city: params.city,
-            domain: params.domain,
+fn validate_page(page: &PageRequest) -> Result<(), OfferError> {
+    if page.limit == 0 || page.limit > MAX_PAGE_SIZE {
+        return Err(OfferError::InvalidData(format!(
+            "limit must be between 1 and {}",
+            MAX_PAGE_SIZE
+        )));
+    }
+    Ok(())
+}
+
+/// Validates creation parameters and builds the offer to store, reporting
+/// `errors` found beforehand along with its own.
+fn build_offer(
+    params: CreateOfferParams,
+    mut errors: Vec<FieldError>,
+    rates: &ExchangeRates,
+) -> Result<Offer, OfferError> {
+    let status = params.status.unwrap_or(OfferStatus::Published);
+    if !matches!(status, OfferStatus::Draft | OfferStatus::Published) {
+        errors.push(FieldError::new("status", "must be draft or published"));
+    }
+    let positions = params.positions.unwrap_or(1);
+    if positions == 0 {
+        errors.push(FieldError::new("positions", "must be at least 1"));
+    }
+    let start_date = parse_date("start_date", &params.start_date, &mut errors);
+    let end_date = parse_date("end_date", &params.end_date, &mut errors);
+    let location = locate(&params.city, params.latitude, params.longitude, &mut errors);
+    let skills = validate_skills(params.skills, &mut errors);
+    let description = validate_description(params.description, &mut errors);
+    let publish_at = validate_publish_at(params.publish_at, end_date, Utc::now(), &mut errors);
+    let salary_period = params.salary_period.unwrap_or_default();
+    let (currency, salary_monthly_eur) = normalize_salary(
+        params.salary,
+        params.currency.as_deref().unwrap_or(BASE_CURRENCY),
+        salary_period,
+        rates,
+        &mut errors,
+    );
+    let (start_date, end_date) = validate_fields(
+        &OfferFields {
+            title: &params.title,
+            link: &params.link,
+            city: &params.city,
+            domain: &params.domain,
             salary: params.salary,
-            start_date: params.start_date,
-            end_date: params.end_date,
-            available: true,
+            start_date,
+            end_date,
+        },
+        errors,
+    )?;
+
+    let mut offer = Offer {
+        id: Uuid::new_v4().to_string(),
+        title: params.title.trim().to_string(),
+        link: params.link,
+        city: params.city.trim().to_string(),
+        domain: params.domain.trim().to_string(),
+        description,
+        skills,
+        location,
+        salary: params.salary,
+        currency,
+        salary_period,
+        salary_monthly_eur,
+        start_date,
+        end_date,
+        status,
+        company_id: company_ref(params.company_id),
+        positions,
+        remaining: positions,
+        available: false,
+        deleted_at: None,
+        publish_at: None,
+        version: 1,
+        distance_km: None,
+    };
+    offer.set_status(status);
+    if let Some(at) = publish_at {
+        offer.schedule(at);
+    }
+
+    Ok(offer)
+}
+
+pub struct OfferService<R: OfferRepository, C: CompanyRepository> {
+    repository: R,
+    companies: Arc<C>,
+    rates: ExchangeRates,
+}
+
+impl<R: OfferRepository, C: CompanyRepository> OfferService<R, C> {
+    pub fn new(repository: R, companies: Arc<C>, rates: ExchangeRates) -> Self {
+        Self {
+            repository,
+            companies,
+            rates,
+        }
+    }
+
+    /// Reports a company reference that matches no company. `known` remembers
+    /// lookups, so that import rows sharing a company only look it up once.
+    async fn check_company(
+        &self,
+        company_id: Option<&str>,
+        known: &mut HashMap<String, bool>,
+    ) -> Result<Vec<FieldError>, OfferError> {
+        let Some(id) = company_id else {
+            return Ok(Vec::new());
+        };
+        let exists = match known.get(id) {
+            Some(&exists) => exists,
+            None => {
+                let exists = self
+                    .companies
+                    .get_by_id(id)
+                    .await
+                    .map_err(|e| OfferError::DatabaseError(e.to_string()))?
+                    .is_some();
+                known.insert(id.to_string(), exists);
+                exists
+            }
+        };
+        Ok(if exists {
+            Vec::new()
+        } else {
+            vec![FieldError::new("company_id", "does not match any company")]
+        })
+    }
+
+    async fn prepare_offer(&self, mut params: CreateOfferParams) -> Result<Offer, OfferError> {
+        params.company_id = company_ref(params.company_id);
+        let errors = self
+            .check_company(params.company_id.as_deref(), &mut HashMap::new())
+            .await?;
+        build_offer(params, errors, &self.rates)
+    }
+
+    pub async fn create_offer(
+        &self,
+        params: CreateOfferParams,
+        actor: Option<&str>,
+    ) -> Result<Offer, OfferError> {
+        let offer = self.prepare_offer(params).await?;
+        self.repository
+            .create(&offer, &ChangeLog::created(&offer, actor))
+            .await
+    }
+
+    /// Creates the offer once per `key`: retrying the same `request` within
+    /// [`IDEMPOTENCY_KEY_TTL`] returns the offer created the first time, while
+    /// another request reusing the key fails. Failed attempts are not
+    /// remembered, so they can be retried.
+    ///
+    /// [`IDEMPOTENCY_KEY_TTL`]: crate::domain::idempotency::IDEMPOTENCY_KEY_TTL
+    pub async fn create_offer_idempotent(
+        &self,
+        params: CreateOfferParams,
+        key: &str,
+        request: &str,
+        actor: Option<&str>,
+    ) -> Result<CreatedOffer, OfferError> {
+        if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(OfferError::InvalidData(format!(
+                "Idempotency-Key must be between 1 and {} characters",
+                MAX_IDEMPOTENCY_KEY_LEN
+            )));
+        }
+        if let Some(replayed) = self.replay(key, request).await? {
+            return Ok(replayed);
+        }
+
+        let offer = self.prepare_offer(params).await?;
+        let record = IdempotencyRecord::new(key, request, &offer, Utc::now());
+        match self
+            .repository
+            .create_idempotent(&offer, &ChangeLog::created(&offer, actor), &record)
+            .await
+        {
+            Ok(offer) => Ok(CreatedOffer {
+                offer,
+                replayed: false,
+            }),
+            // A concurrent attempt stored its offer first.
+            Err(OfferError::IdempotencyKeyReused) => self
+                .replay(key, request)
+                .await?
+                .ok_or(OfferError::IdempotencyKeyReused),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn replay(&self, key: &str, request: &str) -> Result<Option<CreatedOffer>, OfferError> {
+        match self.repository.find_idempotency_record(key).await? {
+            Some(record) if record.request == request => Ok(Some(CreatedOffer {
+                offer: record.offer,
+                replayed: true,
+            })),
+            Some(_) => Err(OfferError::IdempotencyKeyReused),
+            None => Ok(None),
+        }
+    }
+
+    /// Validates every row and inserts the valid ones in batches, reporting
+    /// what was accepted and why each other row was rejected.
+    pub async fn import_offers(&self, rows: Vec<ImportRow>, actor: Option<&str>) -> ImportReport {
+        let mut report = ImportReport {
+            received: rows.len(),
+            accepted: Vec::new(),
+            errors: Vec::new(),
         };
-        self.repository.create(&offer).await
+
+        let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+        let mut known_companies = HashMap::new();
+        for row in rows {
+            let built = match row.params {
+                Ok(mut params) => {
+                    params.company_id = company_ref(params.company_id);
+                    match self
+                        .check_company(params.company_id.as_deref(), &mut known_companies)
+                        .await
+                    {
+                        Ok(errors) => build_offer(params, errors, &self.rates),
+                        Err(e) => Err(e),
+                    }
+                }
+                Err(message) => Err(OfferError::InvalidData(message)),
+            };
+            let checked = match built {
+                Ok(offer) => self.check_duplicate(offer, &batch).await,
+                Err(e) => Err(e),
+            };
+            match checked {
+                Ok(offer) => batch.push((row.row, offer)),
+                Err(e) => report.errors.push(ImportRowError::new(row.row, e)),
+            }
+            if batch.len() == IMPORT_BATCH_SIZE {
+                self.insert_batch(std::mem::take(&mut batch), actor, &mut report)
+                    .await;
+            }
+        }
+        if !batch.is_empty() {
+            self.insert_batch(batch, actor, &mut report).await;
+        }
+
+        report.errors.sort_by_key(|error| error.row);
+        report
+    }
+
+    /// Rejects an imported offer that duplicates a stored one or one of the
+    /// `pending` rows, so that it does not fail its whole batch.
+    async fn check_duplicate(
+        &self,
+        offer: Offer,
+        pending: &[(usize, Offer)],
+    ) -> Result<Offer, OfferError> {
+        let existing = match pending.iter().find(|(_, other)| other.duplicates(&offer)) {
+            Some((_, other)) => Some(other.id.clone()),
+            None => self
+                .repository
+                .find_duplicate(&offer)
+                .await?
+                .map(|other| other.id),
+        };
+        match existing {
+            Some(existing_id) => Err(OfferError::Duplicate { existing_id }),
+            None => Ok(offer),
+        }
+    }
+
+    async fn insert_batch(
+        &self,
+        batch: Vec<(usize, Offer)>,
+        actor: Option<&str>,
+        report: &mut ImportReport,
+    ) {
+        let (rows, offers): (Vec<usize>, Vec<Offer>) = batch.into_iter().unzip();
+        let mut log = ChangeLog::default();
+        for offer in &offers {
+            log.extend(ChangeLog::created(offer, actor));
+        }
+
+        match self.repository.create_many(&offers, &log).await {
+            Ok(()) => report.accepted.extend(
+                rows.into_iter()
+                    .zip(offers)
+                    .map(|(row, offer)| ImportedRow { row, id: offer.id }),
+            ),
+            Err(e) => {
+                let message = e.to_string();
+                report
+                    .errors
+                    .extend(rows.into_iter().map(|row| ImportRowError {
+                        row,
+                        error: message.clone(),
+                        fields: Vec::new(),
+                        duplicate_of: None,
+                    }));
+            }
+        }
     }
 
+    /// Returns the offer even if it was deleted, so that past applications can
+    /// still show what they were for.
     pub async fn get_offer(&self, id: &str) -> Result<Offer, OfferError> {
-        let offer = self.repository.get_by_id(id).await?;
-        match offer {
-            Some(o) if o.available => Ok(o),
-            _ => Err(OfferError::NotFound),
+        self.repository
+            .get_by_id(id)
+            .await?
+            .ok_or(OfferError::NotFound)
+    }
+
+    /// Like [`Self::get_offer`], but deleted offers count as missing.
+    async fn get_live_offer(&self, id: &str) -> Result<Offer, OfferError> {
+        match self.get_offer(id).await? {
+            offer if offer.is_deleted() => Err(OfferError::NotFound),
+            offer => Ok(offer),
         }
     }
 
-    pub async fn list_offers_by_domain(&self, domain: &str) -> Result<Vec<Offer>, OfferError> {
-        let offers = self.repository.list_by_domain(domain).await?;
-        Ok(offers.into_iter().filter(|o| o.available).collect())
+    pub async fn search_offers(
+        &self,
+        mut query: OfferQuery,
+        page: PageRequest,
+    ) -> Result<OfferPage, OfferError> {
+        validate_page(&page)?;
+        prepare_query(&mut query)?;
+        self.repository.search(&query, &page).await
+    }
+
+    /// Same filters as [`Self::search_offers`], counted per filterable value.
+    pub async fn offer_facets(&self, mut query: OfferQuery) -> Result<OfferFacets, OfferError> {
+        prepare_query(&mut query)?;
+        self.repository.facets(&query).await
     }
 
-    pub async fn list_offers_by_city(&self, city: &str) -> Result<Vec<Offer>, OfferError> {
-        let offers = self.repository.list_by_city(city).await?;
-        Ok(offers.into_iter().filter(|o| o.available).collect())
+    pub async fn export_offers(
+        &self,
+        mut query: OfferQuery,
+    ) -> Result<BoxStream<'static, Result<Offer, OfferError>>, OfferError> {
+        prepare_query(&mut query)?;
+        self.repository.stream(&query).await
     }
 
+    pub async fn browse_offers(&self, page: PageRequest) -> Result<OfferPage, OfferError> {
+        validate_page(&page)?;
+        self.repository.list_all(&page).await
+    }
+
+    /// With `if_match`, only updates an offer still at one of those versions.
     pub async fn update_offer(
         &self,
         id: &str,
         params: UpdateOfferParams,
-    ) -> Result<Offer, OfferError> {
-        let existing = self
-            .repository
-            .get_by_id(id)
-            .await?
-            .ok_or(OfferError::NotFound)?;
+        if_match: Option<&[u64]>,
+        actor: Option<&str>,
+    ) -> Result<OfferUpdate, OfferError> {
+        let existing = self.get_live_offer(id).await?;
+        if let Some(versions) = if_match
+            && !versions.contains(&existing.version)
+        {
+            return Err(OfferError::VersionMismatch {
+                current: existing.version,
+            });
+        }
+
+        let mut errors = Vec::new();
+        let start_date = match &params.start_date {
+            Some(raw) => parse_date("start_date", raw, &mut errors),
+            None => Some(existing.start_date),
+        };
+        let end_date = match &params.end_date {
+            Some(raw) => parse_date("end_date", raw, &mut errors),
+            None => Some(existing.end_date),
+        };
+
+        let title = params.title.unwrap_or_else(|| existing.title.clone());
+        let link = params.link.unwrap_or_else(|| existing.link.clone());
+        let city = params.city.unwrap_or_else(|| existing.city.clone());
+        let domain = params.domain.unwrap_or_else(|| existing.domain.clone());
+        let salary = params.salary.unwrap_or(existing.salary);
+        let salary_period = params.salary_period.unwrap_or(existing.salary_period);
+        let (currency, salary_monthly_eur) = normalize_salary(
+            salary,
+            params.currency.as_deref().unwrap_or(&existing.currency),
+            salary_period,
+            &self.rates,
+            &mut errors,
+        );
+        let company_id = match params.company_id {
+            Some(raw) => {
+                let company_id = company_ref(Some(raw));
+                if company_id != existing.company_id {
+                    errors.extend(
+                        self.check_company(company_id.as_deref(), &mut HashMap::new())
+                            .await?,
+                    );
+                }
+                company_id
+            }
+            None => existing.company_id.clone(),
+        };
+        let skills = match params.skills {
+            Some(skills) => validate_skills(skills, &mut errors),
+            None => existing.skills.clone(),
+        };
+        let description = match params.description {
+            Some(description) => validate_description(Some(description), &mut errors),
+            None => existing.description.clone(),
+        };
+        let location = match (params.latitude, params.longitude) {
+            (None, None) if city.trim() == existing.city => existing.location,
+            (latitude, longitude) => locate(&city, latitude, longitude, &mut errors),
+        };
+        let publish_at = match params.publish_at {
+            Some(_) if !existing.is_scheduled() => {
+                errors.push(FieldError::new(
+                    "publish_at",
+                    "cannot be set on a live offer",
+                ));
+                None
+            }
+            Some(at) => validate_publish_at(Some(at), end_date, Utc::now(), &mut errors),
+            None => existing.publish_at,
+        };
+
+        // Seats already taken stay taken when the number of positions changes.
+        let taken = existing.positions.saturating_sub(existing.remaining);
+        let positions = params.positions.unwrap_or(existing.positions);
+        if positions == 0 || positions < taken {
+            errors.push(FieldError::new(
+                "positions",
+                format!(
+                    "must be at least {}, the number of seats already taken",
+                    taken.max(1)
+                ),
+            ));
+        }
+        let remaining = positions.saturating_sub(taken);
+
+        let (start_date, end_date) = validate_fields(
+            &OfferFields {
+                title: &title,
+                link: &link,
+                city: &city,
+                domain: &domain,
+                salary,
+                start_date,
+                end_date,
+            },
+            errors,
+        )?;
 
-        let updated = Offer {
-            id: existing.id,
-            title: params.title.unwrap_or(existing.title),
-            link: params.link.unwrap_or(existing.link),
-            city: params.city.unwrap_or(existing.city),
-            domain: params.domain.unwrap_or(existing.domain),
-            salary: params.salary.unwrap_or(existing.salary),
-            start_date: params.start_date.unwrap_or(existing.start_date),
-            end_date: params.end_date.unwrap_or(existing.end_date),
-            available: params.available.unwrap_or(existing.available),
+        // The legacy flag only publishes drafts; other states go through /transition.
+        let status = match (params.available, existing.status) {
+            (Some(true), OfferStatus::Draft | OfferStatus::Published) => OfferStatus::Published,
+            (Some(true), from) => {
+                return Err(OfferError::InvalidTransition {
+                    from,
+                    to: OfferStatus::Published,
+                });
+            }
+            (Some(false), OfferStatus::Published) => OfferStatus::Filled,
+            (None, OfferStatus::Published) if remaining == 0 => OfferStatus::Filled,
+            (None, OfferStatus::Filled) if params.positions.is_some() && remaining > 0 => {
+                OfferStatus::Published
+            }
+            _ => existing.status,
         };
+        ensure_transition(existing.status, status, remaining)?;
+
+        let mut updated = Offer {
+            id: existing.id.clone(),
+            title: title.trim().to_string(),
+            link,
+            city: city.trim().to_string(),
+            domain: domain.trim().to_string(),
+            description,
+            skills,
+            location,
+            salary,
+            currency,
+            salary_period,
+            salary_monthly_eur,
+            start_date,
+            end_date,
+            status: existing.status,
+            company_id,
+            positions,
+            remaining,
+            available: existing.available,
+            deleted_at: None,
+            publish_at: None,
+            version: existing.version,
+            distance_km: None,
+        };
+        updated.set_status(status);
+        if let Some(at) = publish_at {
+            updated.schedule(at);
+        }
+
+        let update = OfferUpdate::new(&existing, updated);
+        self.repository
+            .update(&update.offer, &ChangeLog::updated(&update, actor))
+            .await?;
+        Ok(update)
+    }
+
+    pub async fn transition_offer(
+        &self,
+        id: &str,
+        next: OfferStatus,
+        actor: Option<&str>,
+    ) -> Result<OfferUpdate, OfferError> {
+        let existing = self.get_live_offer(id).await?;
+
+        if existing.status == next {
+            return Ok(OfferUpdate {
+                offer: existing,
+                changes: Vec::new(),
+            });
+        }
+        ensure_transition(existing.status, next, existing.remaining)?;
+
+        let mut offer = existing.clone();
+        offer.set_status(next);
+        let update = OfferUpdate::new(&existing, offer);
+        self.repository
+            .update(&update.offer, &ChangeLog::updated(&update, actor))
+            .await?;
+        Ok(update)
+    }
+
+    pub async fn reserve_offer(
+        &self,
+        id: &str,
+        actor: Option<&str>,
+    ) -> Result<Reservation, OfferError> {
+        if let Some(offer) = self.repository.reserve(id, actor).await? {
+            return Ok(Reservation::Reserved(Box::new(offer)));
+        }
+
+        match self.repository.get_by_id(id).await? {
+            Some(offer) if !offer.is_deleted() && !offer.is_scheduled() => {
+                Ok(Reservation::AlreadyTaken)
+            }
+            _ => Err(OfferError::NotFound),
+        }
+    }
+
+    /// Moves every published offer whose `reference` date is before `today` to
+    /// `expired` and returns the offers that changed. Offers edited meanwhile
+    /// are left for the next sweep.
+    pub async fn expire_offers(
+        &self,
+        reference: ExpiryReference,
+        today: NaiveDate,
+    ) -> Result<Vec<Offer>, OfferError> {
+        let due = self.repository.list_past_due(reference, today).await?;
+
+        let mut expired = Vec::with_capacity(due.len());
+        for previous in due {
+            let mut offer = previous.clone();
+            offer.set_status(OfferStatus::Expired);
+            let update = OfferUpdate::new(&previous, offer);
+            let log = ChangeLog {
+                events: vec![OfferEvent::expired(&update.offer)],
+                history: vec![HistoryEntry::updated(&update, Some(EXPIRY_ACTOR))],
+            };
+            match self.repository.update(&update.offer, &log).await {
+                Ok(offer) => expired.push(offer),
+                Err(err) => skip_conflict(EXPIRY_ACTOR, &previous.id, err)?,
+            }
+        }
+        Ok(expired)
+    }
 
-        self.repository.update(&updated).await
+    /// Puts every offer whose scheduled publication is due at `now` live,
+    /// announcing each with `offer.created`, and returns them. Offers edited
+    /// meanwhile wait for the next run.
+    pub async fn publish_due_offers(&self, now: DateTime<Utc>) -> Result<Vec<Offer>, OfferError> {
+        let due = self.repository.list_due_for_publication(now).await?;
+
+        let mut published = Vec::with_capacity(due.len());
+        for previous in due {
+            let mut offer = previous.clone();
+            offer.publish_at = None;
+            let update = OfferUpdate::new(&previous, offer);
+            let log = ChangeLog::updated(&update, Some(PUBLICATION_ACTOR));
+            match self.repository.update(&update.offer, &log).await {
+                Ok(offer) => published.push(offer),
+                Err(err) => skip_conflict(PUBLICATION_ACTOR, &previous.id, err)?,
+            }
+        }
+        Ok(published)
+    }
+
+    /// History of an offer, oldest first. Still available once the offer is deleted.
+    pub async fn offer_history(&self, id: &str) -> Result<Vec<HistoryEntry>, OfferError> {
+        let history = self.repository.history(id).await?;
+        if history.is_empty() {
+            // Offers created before history was recorded have none yet.
+            self.get_offer(id).await?;
+        }
+        Ok(history)
+    }
+
+    /// Soft-deletes the offer and returns it with its deletion marker.
+    pub async fn delete_offer(&self, id: &str, actor: Option<&str>) -> Result<Offer, OfferError> {
+        let existing = self.get_live_offer(id).await?;
+        let mut offer = existing.clone();
+        offer.mark_deleted(Utc::now());
+
+        let update = OfferUpdate::new(&existing, offer);
+        self.repository
+            .update(&update.offer, &ChangeLog::deleted(&update, actor))
+            .await
+    }
+
+    pub async fn restore_offer(&self, id: &str, actor: Option<&str>) -> Result<Offer, OfferError> {
+        let existing = self.get_offer(id).await?;
+        if !existing.is_deleted() {
+            return Err(OfferError::InvalidData("Offer is not deleted".to_string()));
+        }
+        let mut offer = existing.clone();
+        offer.restore();
+        // The company may have been deleted while the offer was.
+        if !self
+            .check_company(offer.company_id.as_deref(), &mut HashMap::new())
+            .await?
+            .is_empty()
+        {
+            offer.company_id = None;
+        }
+
+        let update = OfferUpdate::new(&existing, offer);
+        self.repository
+            .update(&update.offer, &ChangeLog::restored(&update, actor))
+            .await
+    }
+
+    /// Number of offers, deleted ones aside and scheduled ones included, that
+    /// reference the company.
+    pub async fn count_company_offers(&self, company_id: &str) -> Result<u64, OfferError> {
+        let query = OfferQuery {
+            company_id: Some(company_id.to_string()),
+            include_scheduled: true,
+            ..Default::default()
+        };
+        let page = PageRequest {
+            offset: 0,
+            limit: 1,
+            sort: None,
+            order: Default::default(),
+        };
+        Ok(self.repository.search(&query, &page).await?.total)
     }
 
-    pub async fn delete_offer(&self, id: &str) -> Result<(), OfferError> {
-        self.repository.delete(id).await
+    /// Permanently removes offers deleted before `before`. Their history is kept.
+    pub async fn purge_deleted_offers(&self, before: DateTime<Utc>) -> Result<u64, OfferError> {
+        self.repository.purge_deleted(before).await
     }
 }
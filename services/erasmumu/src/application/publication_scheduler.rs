@@ -0,0 +1,22 @@
+use crate::application::offer_service::OfferService;
+use crate::domain::offer::Offer;
+use crate::domain::ports::clock::Clock;
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::{OfferError, OfferRepository};
+use std::sync::Arc;
+
+pub struct PublicationScheduler<R: OfferRepository, C: CompanyRepository, K: Clock> {
+    service: Arc<OfferService<R, C>>,
+    clock: K,
+}
+
+impl<R: OfferRepository, C: CompanyRepository, K: Clock> PublicationScheduler<R, C, K> {
+    pub fn new(service: Arc<OfferService<R, C>>, clock: K) -> Self {
+        Self { service, clock }
+    }
+
+    /// Puts live the offers whose `publish_at` has come.
+    pub async fn publish(&self) -> Result<Vec<Offer>, OfferError> {
+        self.service.publish_due_offers(self.clock.now()).await
+    }
+}
@@ -0,0 +1,27 @@
+use crate::application::offer_service::OfferService;
+use crate::domain::ports::clock::Clock;
+use crate::domain::ports::company_repository::CompanyRepository;
+use crate::domain::ports::offer_repository::{OfferError, OfferRepository};
+use std::sync::Arc;
+
+pub struct TombstonePurger<R: OfferRepository, C: CompanyRepository, K: Clock> {
+    service: Arc<OfferService<R, C>>,
+    clock: K,
+    retention: chrono::Duration,
+}
+
+impl<R: OfferRepository, C: CompanyRepository, K: Clock> TombstonePurger<R, C, K> {
+    pub fn new(service: Arc<OfferService<R, C>>, clock: K, retention: chrono::Duration) -> Self {
+        Self {
+            service,
+            clock,
+            retention,
+        }
+    }
+
+    /// Removes offers deleted longer than `retention` ago.
+    pub async fn purge(&self) -> Result<u64, OfferError> {
+        let before = self.clock.now() - self.retention;
+        self.service.purge_deleted_offers(before).await
+    }
+}
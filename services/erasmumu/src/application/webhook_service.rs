@@ -0,0 +1,251 @@
+use crate::application::offer_service::is_web_url;
+use crate::domain::events::OfferEvent;
+use crate::domain::outbox::OutboxMessage;
+use crate::domain::ports::offer_repository::FieldError;
+use crate::domain::ports::webhook_repository::{WebhookError, WebhookRepository};
+use crate::domain::ports::webhook_sender::WebhookSender;
+use crate::domain::webhook::{
+    DELIVERY_HEADER, DeliveryAttempt, EVENT_HEADER, MIN_SECRET_LEN, SIGNATURE_HEADER,
+    WebhookDelivery, WebhookSubscription, signature,
+};
+use chrono::{DateTime, Utc};
+use futures_util::{StreamExt, TryStreamExt, stream};
+use uuid::Uuid;
+
+/// Deliveries sent per call to [`WebhookService::deliver_due`].
+const DELIVERY_BATCH_SIZE: u64 = 50;
+/// Subscriptions [`WebhookService::deliver_due`] sends to at the same time.
+const CONCURRENT_SUBSCRIPTIONS: usize = 8;
+pub const MAX_DELIVERY_LOG_SIZE: u64 = 100;
+
+pub struct CreateSubscriptionParams {
+    pub url: String,
+    pub events: Vec<String>,
+    /// Generated when missing.
+    pub secret: Option<String>,
+}
+
+pub struct UpdateSubscriptionParams {
+    pub url: Option<String>,
+    /// An empty list subscribes to every event.
+    pub events: Option<Vec<String>>,
+    /// Rotates the secret.
+    pub secret: Option<String>,
+}
+
+/// Checks every field and reports all violations at once, returning the
+/// subscription with trimmed values and de-duplicated events.
+fn validate_subscription(
+    mut subscription: WebhookSubscription,
+) -> Result<WebhookSubscription, WebhookError> {
+    let mut errors = Vec::new();
+
+    subscription.url = subscription.url.trim().to_string();
+    if !is_web_url(&subscription.url) {
+        errors.push(FieldError::new("url", "must be an absolute http(s) URL"));
+    }
+
+    let mut events: Vec<String> = Vec::with_capacity(subscription.events.len());
+    for event in &subscription.events {
+        let event = event.trim();
+        if !OfferEvent::ROUTING_KEYS.contains(&event) {
+            errors.push(FieldError::new(
+                "events",
+                format!(
+                    "must only list {}, not {:?}",
+                    OfferEvent::ROUTING_KEYS.join(", "),
+                    event
+                ),
+            ));
+        } else if !events.iter().any(|known| known == event) {
+            events.push(event.to_string());
+        }
+    }
+    subscription.events = events;
+
+    if subscription.secret.chars().count() < MIN_SECRET_LEN {
+        errors.push(FieldError::new(
+            "secret",
+            format!("must be at least {} characters long", MIN_SECRET_LEN),
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(subscription)
+    } else {
+        Err(WebhookError::ValidationFailed(errors))
+    }
+}
+
+fn generate_secret() -> String {
+    format!("whsec_{}", Uuid::new_v4().simple())
+}
+
+pub struct WebhookService<W: WebhookRepository, S: WebhookSender> {
+    repository: W,
+    sender: S,
+}
+
+impl<W: WebhookRepository, S: WebhookSender> WebhookService<W, S> {
+    pub fn new(repository: W, sender: S) -> Self {
+        Self { repository, sender }
+    }
+
+    pub async fn create_subscription(
+        &self,
+        params: CreateSubscriptionParams,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        let subscription = validate_subscription(WebhookSubscription {
+            id: Uuid::new_v4().to_string(),
+            url: params.url,
+            events: params.events,
+            secret: params.secret.unwrap_or_else(generate_secret),
+            created_at: Utc::now(),
+        })?;
+        self.repository.create_subscription(&subscription).await
+    }
+
+    pub async fn get_subscription(&self, id: &str) -> Result<WebhookSubscription, WebhookError> {
+        self.repository
+            .get_subscription(id)
+            .await?
+            .ok_or(WebhookError::NotFound)
+    }
+
+    pub async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, WebhookError> {
+        self.repository.list_subscriptions().await
+    }
+
+    pub async fn update_subscription(
+        &self,
+        id: &str,
+        params: UpdateSubscriptionParams,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        let existing = self.get_subscription(id).await?;
+        let subscription = validate_subscription(WebhookSubscription {
+            url: params.url.unwrap_or(existing.url),
+            events: params.events.unwrap_or(existing.events),
+            secret: params.secret.unwrap_or(existing.secret),
+            ..existing
+        })?;
+        self.repository.update_subscription(&subscription).await
+    }
+
+    pub async fn delete_subscription(&self, id: &str) -> Result<(), WebhookError> {
+        self.repository.delete_subscription(id).await
+    }
+
+    /// Latest deliveries to the subscription, newest first, with every attempt.
+    pub async fn deliveries(
+        &self,
+        subscription_id: &str,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        self.get_subscription(subscription_id).await?;
+        self.repository
+            .deliveries(subscription_id, limit.clamp(1, MAX_DELIVERY_LOG_SIZE))
+            .await
+    }
+
+    /// Queues the event for every subscription that wants it and returns how
+    /// many deliveries that made.
+    pub async fn fan_out(
+        &self,
+        message: &OutboxMessage,
+        now: DateTime<Utc>,
+    ) -> Result<usize, WebhookError> {
+        let deliveries: Vec<WebhookDelivery> = self
+            .repository
+            .list_subscriptions()
+            .await?
+            .iter()
+            .filter(|subscription| subscription.wants(&message.routing_key))
+            .map(|subscription| WebhookDelivery::new(subscription, message, now))
+            .collect();
+        self.repository.enqueue(&deliveries).await?;
+        Ok(deliveries.len())
+    }
+
+    /// Attempts the deliveries due at `now` and returns them as they are
+    /// afterwards: delivered, waiting for a retry, or given up on. Subscriptions
+    /// are sent to concurrently, each one's deliveries in order; once an
+    /// endpoint fails, its other deliveries wait for the next run rather than
+    /// for its timeout again.
+    pub async fn deliver_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        let due = self
+            .repository
+            .due_deliveries(now, DELIVERY_BATCH_SIZE)
+            .await?;
+
+        let mut by_subscription: Vec<(String, Vec<WebhookDelivery>)> = Vec::new();
+        for delivery in due {
+            match by_subscription
+                .iter_mut()
+                .find(|(id, _)| *id == delivery.subscription_id)
+            {
+                Some((_, deliveries)) => deliveries.push(delivery),
+                None => by_subscription.push((delivery.subscription_id.clone(), vec![delivery])),
+            }
+        }
+
+        let attempted: Vec<Vec<WebhookDelivery>> = stream::iter(by_subscription)
+            .map(|(subscription_id, deliveries)| self.deliver_to(subscription_id, deliveries, now))
+            .buffer_unordered(CONCURRENT_SUBSCRIPTIONS)
+            .try_collect()
+            .await?;
+        Ok(attempted.into_iter().flatten().collect())
+    }
+
+    async fn deliver_to(
+        &self,
+        subscription_id: String,
+        deliveries: Vec<WebhookDelivery>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        let subscription = self.repository.get_subscription(&subscription_id).await?;
+
+        let mut attempted = Vec::with_capacity(deliveries.len());
+        for mut delivery in deliveries {
+            let Some(subscription) = &subscription else {
+                // Deleted after the delivery was queued.
+                delivery.abandon(now, "subscription was deleted");
+                self.repository.save_delivery(&delivery).await?;
+                attempted.push(delivery);
+                continue;
+            };
+
+            let body = delivery.body();
+            let headers = [
+                (EVENT_HEADER, delivery.event.clone()),
+                (DELIVERY_HEADER, delivery.id.clone()),
+                (
+                    SIGNATURE_HEADER,
+                    signature(&subscription.secret, now.timestamp(), &body),
+                ),
+            ];
+            let attempt = match self.sender.send(&subscription.url, &headers, body).await {
+                Ok(status_code) => DeliveryAttempt {
+                    at: now,
+                    status_code: Some(status_code),
+                    error: None,
+                },
+                Err(error) => DeliveryAttempt {
+                    at: now,
+                    status_code: None,
+                    error: Some(error),
+                },
+            };
+            let failed = !attempt.succeeded();
+            delivery.record(attempt);
+            self.repository.save_delivery(&delivery).await?;
+            attempted.push(delivery);
+            if failed {
+                break;
+            }
+        }
+        Ok(attempted)
+    }
+}
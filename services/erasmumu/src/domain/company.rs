@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// An employer posting offers. Offers point to it through `Offer::company_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Company {
+    pub id: String,
+    pub name: String,
+    pub website: Option<String>,
+    pub description: Option<String>,
+    /// Cities the company hires in.
+    #[serde(default)]
+    pub cities: Vec<String>,
+}
@@ -0,0 +1,139 @@
+use crate::domain::offer::{FieldChange, Offer, OfferStatus};
+use serde::Serialize;
+
+/// Events published on the `polymove.events` exchange whenever an offer changes.
+/// Most carry the offer's `status`, so that consumers can tell live offers
+/// from drafts and closed ones.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum OfferEvent {
+    Created {
+        id: String,
+        title: String,
+        city: String,
+        domain: String,
+        status: OfferStatus,
+        salary: f64,
+    },
+    Updated {
+        id: String,
+        title: String,
+        city: String,
+        domain: String,
+        status: OfferStatus,
+        changes: Vec<FieldChange>,
+    },
+    Deleted {
+        id: String,
+        title: String,
+        city: String,
+        domain: String,
+        status: OfferStatus,
+    },
+    Restored {
+        id: String,
+        title: String,
+        city: String,
+        domain: String,
+        status: OfferStatus,
+    },
+    Filled {
+        id: String,
+        title: String,
+        city: String,
+        domain: String,
+        positions: u32,
+    },
+    Expired {
+        id: String,
+        title: String,
+        city: String,
+        domain: String,
+        expired_at: String,
+    },
+}
+
+impl OfferEvent {
+    /// Every value [`Self::routing_key`] can return.
+    pub const ROUTING_KEYS: [&'static str; 6] = [
+        "offer.created",
+        "offer.updated",
+        "offer.deleted",
+        "offer.restored",
+        "offer.filled",
+        "offer.expired",
+    ];
+
+    pub fn created(offer: &Offer) -> Self {
+        OfferEvent::Created {
+            id: offer.id.clone(),
+            title: offer.title.clone(),
+            city: offer.city.clone(),
+            domain: offer.domain.clone(),
+            status: offer.status,
+            salary: offer.salary,
+        }
+    }
+
+    pub fn updated(offer: &Offer, changes: Vec<FieldChange>) -> Self {
+        OfferEvent::Updated {
+            id: offer.id.clone(),
+            title: offer.title.clone(),
+            city: offer.city.clone(),
+            domain: offer.domain.clone(),
+            status: offer.status,
+            changes,
+        }
+    }
+
+    pub fn deleted(offer: &Offer) -> Self {
+        OfferEvent::Deleted {
+            id: offer.id.clone(),
+            title: offer.title.clone(),
+            city: offer.city.clone(),
+            domain: offer.domain.clone(),
+            status: offer.status,
+        }
+    }
+
+    pub fn restored(offer: &Offer) -> Self {
+        OfferEvent::Restored {
+            id: offer.id.clone(),
+            title: offer.title.clone(),
+            city: offer.city.clone(),
+            domain: offer.domain.clone(),
+            status: offer.status,
+        }
+    }
+
+    pub fn filled(offer: &Offer) -> Self {
+        OfferEvent::Filled {
+            id: offer.id.clone(),
+            title: offer.title.clone(),
+            city: offer.city.clone(),
+            domain: offer.domain.clone(),
+            positions: offer.positions,
+        }
+    }
+
+    pub fn expired(offer: &Offer) -> Self {
+        OfferEvent::Expired {
+            id: offer.id.clone(),
+            title: offer.title.clone(),
+            city: offer.city.clone(),
+            domain: offer.domain.clone(),
+            expired_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn routing_key(&self) -> &'static str {
+        match self {
+            OfferEvent::Created { .. } => "offer.created",
+            OfferEvent::Updated { .. } => "offer.updated",
+            OfferEvent::Deleted { .. } => "offer.deleted",
+            OfferEvent::Restored { .. } => "offer.restored",
+            OfferEvent::Filled { .. } => "offer.filled",
+            OfferEvent::Expired { .. } => "offer.expired",
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+/// Lower bounds of the salary buckets, in euros per month. Each bucket runs up
+/// to the next bound, excluded; the last one has no upper bound.
+pub const SALARY_BUCKET_BOUNDS: [f64; 7] = [0.0, 500.0, 1000.0, 1500.0, 2000.0, 2500.0, 3000.0];
+
+/// How offers matching a query spread over the values the filters offer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OfferFacets {
+    pub total: u64,
+    /// Most common first.
+    pub cities: Vec<FacetCount>,
+    /// Most common first.
+    pub domains: Vec<FacetCount>,
+    /// Every bucket, cheapest first, including empty ones.
+    pub salaries: Vec<SalaryBucket>,
+    /// Months formatted as `YYYY-MM`, earliest first.
+    pub start_months: Vec<FacetCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SalaryBucket {
+    pub min: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    pub count: u64,
+}
+
+/// Index in [`SALARY_BUCKET_BOUNDS`] of the bucket holding `monthly_eur`.
+pub fn salary_bucket(monthly_eur: f64) -> usize {
+    SALARY_BUCKET_BOUNDS
+        .iter()
+        .rposition(|bound| monthly_eur >= *bound)
+        .unwrap_or_default()
+}
+
+/// Buckets with the count found for each of their index.
+pub fn salary_buckets(counts: [u64; SALARY_BUCKET_BOUNDS.len()]) -> Vec<SalaryBucket> {
+    SALARY_BUCKET_BOUNDS
+        .iter()
+        .enumerate()
+        .map(|(index, min)| SalaryBucket {
+            min: *min,
+            max: SALARY_BUCKET_BOUNDS.get(index + 1).copied(),
+            count: counts[index],
+        })
+        .collect()
+}
+
+/// Sorts value counts most common first, then by value.
+pub fn by_count(mut counts: Vec<FacetCount>) -> Vec<FacetCount> {
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    counts
+}
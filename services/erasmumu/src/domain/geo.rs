@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+/// Mean Earth radius used for distances and spherical queries.
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// A position on Earth, stored as a GeoJSON point so that MongoDB can index it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(into = "GeoJsonPoint", try_from = "GeoJsonPoint")]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Checks that both coordinates are in range.
+    pub fn new(lat: f64, lon: f64) -> Result<Self, String> {
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err("latitude must be between -90 and 90".to_string());
+        }
+        if !(-180.0..=180.0).contains(&lon) {
+            return Err("longitude must be between -180 and 180".to_string());
+        }
+        Ok(Self { lat, lon })
+    }
+
+    /// Great-circle distance, by the haversine formula.
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.lon - self.lon).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+impl std::str::FromStr for GeoPoint {
+    type Err = String;
+
+    /// Parses `lat,lon`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (lat, lon) = value
+            .split_once(',')
+            .ok_or_else(|| "near must be formatted as lat,lon".to_string())?;
+        let parse = |raw: &str| {
+            raw.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| "near must be formatted as lat,lon".to_string())
+        };
+        GeoPoint::new(parse(lat)?, parse(lon)?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct GeoJsonPoint {
+    #[serde(rename = "type")]
+    kind: String,
+    /// GeoJSON puts the longitude first.
+    coordinates: [f64; 2],
+}
+
+impl From<GeoPoint> for GeoJsonPoint {
+    fn from(point: GeoPoint) -> Self {
+        Self {
+            kind: "Point".to_string(),
+            coordinates: [point.lon, point.lat],
+        }
+    }
+}
+
+impl TryFrom<GeoJsonPoint> for GeoPoint {
+    type Error = String;
+
+    fn try_from(point: GeoJsonPoint) -> Result<Self, Self::Error> {
+        if point.kind != "Point" {
+            return Err(format!("expected a GeoJSON Point, got {}", point.kind));
+        }
+        let [lon, lat] = point.coordinates;
+        GeoPoint::new(lat, lon)
+    }
+}
+
+/// Restricts a search to offers within `radius_km` of `center`.
+#[derive(Debug, Clone, Copy)]
+pub struct GeoNear {
+    pub center: GeoPoint,
+    pub radius_km: f64,
+}
+
+/// Bundled coordinates of the cities offers are usually posted in, used when an
+/// offer does not give its own.
+const CITIES: &[(&str, f64, f64)] = &[
+    ("Paris", 48.8566, 2.3522),
+    ("Lyon", 45.7640, 4.8357),
+    ("Marseille", 43.2965, 5.3698),
+    ("Toulouse", 43.6047, 1.4442),
+    ("Nice", 43.7102, 7.2620),
+    ("Nantes", 47.2184, -1.5536),
+    ("Strasbourg", 48.5734, 7.7521),
+    ("Montpellier", 43.6108, 3.8767),
+    ("Bordeaux", 44.8378, -0.5792),
+    ("Lille", 50.6292, 3.0573),
+    ("Rennes", 48.1173, -1.6778),
+    ("Reims", 49.2583, 4.0317),
+    ("Grenoble", 45.1885, 5.7245),
+    ("Dijon", 47.3220, 5.0415),
+    ("Angers", 47.4784, -0.5632),
+    ("Nancy", 48.6921, 6.1844),
+    ("Tours", 47.3941, 0.6848),
+    ("Clermont-Ferrand", 45.7772, 3.0870),
+    ("Saint-Etienne", 45.4397, 4.3872),
+    ("Le Havre", 49.4944, 0.1079),
+    ("Rouen", 49.4432, 1.0999),
+    ("Annecy", 45.8992, 6.1294),
+    ("Chambery", 45.5646, 5.9178),
+    ("Sophia Antipolis", 43.6163, 7.0552),
+    ("Brussels", 50.8503, 4.3517),
+    ("Geneva", 46.2044, 6.1432),
+    ("Lausanne", 46.5197, 6.6323),
+    ("Zurich", 47.3769, 8.5417),
+    ("Luxembourg", 49.6116, 6.1319),
+    ("London", 51.5074, -0.1278),
+    ("Dublin", 53.3498, -6.2603),
+    ("Amsterdam", 52.3676, 4.9041),
+    ("Berlin", 52.5200, 13.4050),
+    ("Munich", 48.1351, 11.5820),
+    ("Hamburg", 53.5511, 9.9937),
+    ("Vienna", 48.2082, 16.3738),
+    ("Prague", 50.0755, 14.4378),
+    ("Warsaw", 52.2297, 21.0122),
+    ("Copenhagen", 55.6761, 12.5683),
+    ("Stockholm", 59.3293, 18.0686),
+    ("Oslo", 59.9139, 10.7522),
+    ("Helsinki", 60.1699, 24.9384),
+    ("Madrid", 40.4168, -3.7038),
+    ("Barcelona", 41.3874, 2.1686),
+    ("Valencia", 39.4699, -0.3763),
+    ("Lisbon", 38.7223, -9.1393),
+    ("Porto", 41.1579, -8.6291),
+    ("Rome", 41.9028, 12.4964),
+    ("Milan", 45.4642, 9.1900),
+    ("Turin", 45.0703, 7.6869),
+    ("Athens", 37.9838, 23.7275),
+    ("Budapest", 47.4979, 19.0402),
+];
+
+/// Looks a city up in the bundled table, ignoring case and surrounding spaces.
+pub fn city_coordinates(city: &str) -> Option<GeoPoint> {
+    let city = city.trim();
+    CITIES
+        .iter()
+        .find(|(name, _, _)| name.eq_ignore_ascii_case(city))
+        .map(|&(_, lat, lon)| GeoPoint { lat, lon })
+}
+
+/// Every city of the bundled table with its coordinates.
+pub fn known_cities() -> impl Iterator<Item = (&'static str, GeoPoint)> {
+    CITIES
+        .iter()
+        .map(|&(name, lat, lon)| (name, GeoPoint { lat, lon }))
+}
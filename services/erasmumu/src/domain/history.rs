@@ -0,0 +1,137 @@
+use crate::domain::events::OfferEvent;
+use crate::domain::offer::{FieldChange, Offer, OfferUpdate};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryAction {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+}
+
+/// One change to an offer: when it happened, who made it if known, and which
+/// fields moved from what to what.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub offer_id: String,
+    pub action: HistoryAction,
+    pub actor: Option<String>,
+    pub at: DateTime<Utc>,
+    pub changes: Vec<FieldChange>,
+}
+
+impl HistoryEntry {
+    fn new(
+        offer_id: &str,
+        action: HistoryAction,
+        actor: Option<&str>,
+        changes: Vec<FieldChange>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            offer_id: offer_id.to_string(),
+            action,
+            actor: actor.map(str::to_string),
+            at: Utc::now(),
+            changes,
+        }
+    }
+
+    /// Records every initial value as a change from `null`.
+    pub fn created(offer: &Offer, actor: Option<&str>) -> Self {
+        Self::new(
+            &offer.id,
+            HistoryAction::Created,
+            actor,
+            offer.changes_from_nothing(),
+        )
+    }
+
+    pub fn updated(update: &OfferUpdate, actor: Option<&str>) -> Self {
+        Self::new(
+            &update.offer.id,
+            HistoryAction::Updated,
+            actor,
+            update.changes.clone(),
+        )
+    }
+
+    pub fn deleted(update: &OfferUpdate, actor: Option<&str>) -> Self {
+        Self::new(
+            &update.offer.id,
+            HistoryAction::Deleted,
+            actor,
+            update.changes.clone(),
+        )
+    }
+
+    pub fn restored(update: &OfferUpdate, actor: Option<&str>) -> Self {
+        Self::new(
+            &update.offer.id,
+            HistoryAction::Restored,
+            actor,
+            update.changes.clone(),
+        )
+    }
+}
+
+/// What an offer write leaves behind besides the offer itself: events to relay
+/// and history entries to keep, stored in the same transaction as the write.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeLog {
+    pub events: Vec<OfferEvent>,
+    pub history: Vec<HistoryEntry>,
+}
+
+impl ChangeLog {
+    /// Scheduled offers are announced once they go live, see [`OfferUpdate::events`].
+    pub fn created(offer: &Offer, actor: Option<&str>) -> Self {
+        Self {
+            events: announced(offer, OfferEvent::created),
+            history: vec![HistoryEntry::created(offer, actor)],
+        }
+    }
+
+    /// Empty when nothing actually changed.
+    pub fn updated(update: &OfferUpdate, actor: Option<&str>) -> Self {
+        if update.changes.is_empty() {
+            return Self::default();
+        }
+        Self {
+            events: update.events(),
+            history: vec![HistoryEntry::updated(update, actor)],
+        }
+    }
+
+    pub fn deleted(update: &OfferUpdate, actor: Option<&str>) -> Self {
+        Self {
+            events: announced(&update.offer, OfferEvent::deleted),
+            history: vec![HistoryEntry::deleted(update, actor)],
+        }
+    }
+
+    pub fn restored(update: &OfferUpdate, actor: Option<&str>) -> Self {
+        Self {
+            events: announced(&update.offer, OfferEvent::restored),
+            history: vec![HistoryEntry::restored(update, actor)],
+        }
+    }
+
+    pub fn extend(&mut self, other: ChangeLog) {
+        self.events.extend(other.events);
+        self.history.extend(other.history);
+    }
+}
+
+/// No event for offers the outside world does not know about yet.
+fn announced(offer: &Offer, event: fn(&Offer) -> OfferEvent) -> Vec<OfferEvent> {
+    if offer.is_scheduled() {
+        Vec::new()
+    } else {
+        vec![event(offer)]
+    }
+}
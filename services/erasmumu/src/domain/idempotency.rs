@@ -0,0 +1,34 @@
+use crate::domain::offer::Offer;
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// How long a retry with the same `Idempotency-Key` gets the original response.
+pub const IDEMPOTENCY_KEY_TTL: TimeDelta = TimeDelta::hours(24);
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// An offer created by a request sent with an `Idempotency-Key`, kept so that
+/// retrying the same request returns that offer instead of creating another.
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    /// Canonical form of the request, telling a retry apart from another
+    /// request reusing the key.
+    pub request: String,
+    /// The offer as it was returned when it was created.
+    pub offer: Offer,
+    pub created_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    pub fn new(key: &str, request: &str, offer: &Offer, now: DateTime<Utc>) -> Self {
+        Self {
+            key: key.to_string(),
+            request: request.to_string(),
+            offer: offer.clone(),
+            created_at: now,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.created_at + IDEMPOTENCY_KEY_TTL <= now
+    }
+}
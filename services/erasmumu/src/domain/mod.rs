@@ -1,2 +1,11 @@
+pub mod company;
+pub mod events;
+pub mod facets;
+pub mod geo;
+pub mod history;
+pub mod idempotency;
 pub mod offer;
+pub mod outbox;
 pub mod ports;
+pub mod salary;
+pub mod webhook;
@@ -1,3 +1,7 @@
+use crate::domain::events::OfferEvent;
+use crate::domain::geo::{GeoNear, GeoPoint};
+use crate::domain::salary::{BASE_CURRENCY, SalaryPeriod};
+use chrono::{DateTime, NaiveDate, SubsecRound, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,8 +11,390 @@ pub struct Offer {
     pub link: String,
     pub city: String,
     pub domain: String,
+    /// Free text describing the internship, searched along with the title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Skills the internship asks for, lowercased and without duplicates.
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Where the internship takes place, given with the offer or looked up from
+    /// its city. Offers without one never match a `near` search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoPoint>,
+    /// Amount paid per `salary_period`, in `currency`.
     pub salary: f64,
-    pub start_date: String,
-    pub end_date: String,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    #[serde(default)]
+    pub salary_period: SalaryPeriod,
+    /// `salary` converted to euros per month, which salary filters and sorting
+    /// use so that offers paid differently compare fairly. Zero for offers
+    /// stored in a currency since dropped from the exchange-rate table.
+    #[serde(default)]
+    pub salary_monthly_eur: f64,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub status: OfferStatus,
+    /// The [`Company`](crate::domain::company::Company) posting the offer, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company_id: Option<String>,
+    /// Number of interns the company takes for this offer.
+    #[serde(default = "default_positions")]
+    pub positions: u32,
+    /// Seats still open; the offer is filled once it reaches zero.
+    #[serde(default = "default_positions")]
+    pub remaining: u32,
+    /// Mirrors `status == Published` so that clients reading or writing the
+    /// legacy flag keep working. Change it through [`Offer::set_status`].
     pub available: bool,
+    /// Set when the offer is deleted. Deleted offers are hidden from listings
+    /// but stay readable by id until they are purged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Set while the offer waits for its scheduled publication: until then it
+    /// is hidden from listings, announces none of its changes, and can only be
+    /// read by id. Cleared when the offer goes live.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Starts at 1 and grows by one with every change, so that writers can
+    /// tell whether the offer changed since they read it.
+    #[serde(default = "default_version")]
+    pub version: u64,
+    /// Distance from the center of a `near` search, only set on its results
+    /// and never stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+}
+
+fn default_positions() -> u32 {
+    1
+}
+
+fn default_version() -> u64 {
+    1
+}
+
+fn default_currency() -> String {
+    BASE_CURRENCY.to_string()
+}
+
+impl Offer {
+    /// Leaves the seats alone: an offer with none left can only be published
+    /// again once its positions are raised.
+    pub fn set_status(&mut self, status: OfferStatus) {
+        self.status = status;
+        self.available = status == OfferStatus::Published;
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Marks the offer deleted. Whole seconds keep the stored RFC 3339 strings
+    /// the same length, so they compare in chronological order.
+    pub fn mark_deleted(&mut self, at: DateTime<Utc>) {
+        self.deleted_at = Some(at.trunc_subsecs(0));
+    }
+
+    pub fn restore(&mut self) {
+        self.deleted_at = None;
+    }
+
+    pub fn is_scheduled(&self) -> bool {
+        self.publish_at.is_some()
+    }
+
+    /// Hides the offer until `at`, stored with whole seconds like `deleted_at`.
+    pub fn schedule(&mut self, at: DateTime<Utc>) {
+        self.publish_at = Some(at.trunc_subsecs(0));
+    }
+
+    /// Takes one seat, filling the offer when it was the last one. The
+    /// repository applies the same change atomically when reserving.
+    pub fn take_seat(&mut self) {
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining == 0 {
+            self.set_status(OfferStatus::Filled);
+        }
+    }
+
+    /// Two live offers are the same internship when they share a link, or a
+    /// title, a city and a company regardless of case. Offers of no known
+    /// company only clash on their link.
+    pub fn duplicates(&self, other: &Offer) -> bool {
+        if self.is_deleted() || other.is_deleted() {
+            return false;
+        }
+        self.link == other.link
+            || (self.company_id.is_some()
+                && self.title.to_lowercase() == other.title.to_lowercase()
+                && self.city.to_lowercase() == other.city.to_lowercase()
+                && self.company_id == other.company_id)
+    }
+
+    /// Lists the fields that differ from `previous`, ignoring the id, the
+    /// version, the derived `available` flag and monthly salary, and search
+    /// distances.
+    pub fn changes_from(&self, previous: &Offer) -> Vec<FieldChange> {
+        self.diff(Some(previous))
+    }
+
+    /// Lists every field as a change from `null`, for an offer that was just created.
+    pub fn changes_from_nothing(&self) -> Vec<FieldChange> {
+        self.diff(None)
+    }
+
+    fn diff(&self, previous: Option<&Offer>) -> Vec<FieldChange> {
+        let Ok(serde_json::Value::Object(mut after)) = serde_json::to_value(self) else {
+            return Vec::new();
+        };
+        let before = match previous.map(serde_json::to_value) {
+            Some(Ok(serde_json::Value::Object(before))) => before,
+            _ => serde_json::Map::new(),
+        };
+        // Optional fields are left out once cleared.
+        for field in before.keys() {
+            after
+                .entry(field.clone())
+                .or_insert(serde_json::Value::Null);
+        }
+
+        after
+            .into_iter()
+            .filter(|(field, _)| {
+                !matches!(
+                    field.as_str(),
+                    "id" | "version" | "available" | "salary_monthly_eur" | "distance_km"
+                )
+            })
+            .filter_map(|(field, to)| {
+                let from = before.get(&field).cloned().unwrap_or_default();
+                (from != to).then_some(FieldChange { field, from, to })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferStatus {
+    Draft,
+    Published,
+    Filled,
+    Expired,
+    Archived,
+}
+
+impl OfferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OfferStatus::Draft => "draft",
+            OfferStatus::Published => "published",
+            OfferStatus::Filled => "filled",
+            OfferStatus::Expired => "expired",
+            OfferStatus::Archived => "archived",
+        }
+    }
+
+    /// Archived is terminal; filled and expired offers can be re-published.
+    pub fn can_transition_to(&self, next: OfferStatus) -> bool {
+        use OfferStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Published)
+                | (Draft, Archived)
+                | (Published, Filled)
+                | (Published, Expired)
+                | (Published, Archived)
+                | (Filled, Published)
+                | (Filled, Archived)
+                | (Expired, Published)
+                | (Expired, Archived)
+        )
+    }
+}
+
+impl std::fmt::Display for OfferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single field that differs between two versions of an offer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+/// An offer as stored after a change, with what changed.
+#[derive(Debug, Clone)]
+pub struct OfferUpdate {
+    pub offer: Offer,
+    pub changes: Vec<FieldChange>,
+}
+
+impl OfferUpdate {
+    /// Puts `offer` at the version following `previous`.
+    pub fn new(previous: &Offer, mut offer: Offer) -> Self {
+        offer.version = previous.version + 1;
+        let changes = offer.changes_from(previous);
+        Self { offer, changes }
+    }
+
+    pub fn became(&self, status: OfferStatus) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.field == "status" && change.to == status.as_str())
+    }
+
+    /// Whether the offer was scheduled and is now live.
+    pub fn went_live(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.field == "publish_at" && change.to.is_null())
+    }
+
+    /// `offer.updated` for any change, plus `offer.filled` when the offer just
+    /// got filled. An offer going live is announced as `offer.created` instead,
+    /// and scheduled offers announce nothing.
+    pub fn events(&self) -> Vec<OfferEvent> {
+        if self.offer.is_scheduled() {
+            return Vec::new();
+        }
+        if self.went_live() {
+            return vec![OfferEvent::created(&self.offer)];
+        }
+        let mut events = Vec::new();
+        if !self.changes.is_empty() {
+            events.push(OfferEvent::updated(&self.offer, self.changes.clone()));
+        }
+        if self.became(OfferStatus::Filled) {
+            events.push(OfferEvent::filled(&self.offer));
+        }
+        events
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Reservation {
+    Reserved(Box<Offer>),
+    AlreadyTaken,
+}
+
+/// Which date makes a published offer expire once it is in the past.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpiryReference {
+    StartDate,
+    EndDate,
+}
+
+impl ExpiryReference {
+    pub fn field(&self) -> &'static str {
+        match self {
+            ExpiryReference::StartDate => "start_date",
+            ExpiryReference::EndDate => "end_date",
+        }
+    }
+}
+
+impl std::str::FromStr for ExpiryReference {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "start_date" => Ok(ExpiryReference::StartDate),
+            "end_date" => Ok(ExpiryReference::EndDate),
+            other => Err(format!("unknown expiry reference: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OfferQuery {
+    pub city: Option<String>,
+    pub company_id: Option<String>,
+    pub domain: Option<String>,
+    pub min_salary: Option<f64>,
+    pub max_salary: Option<f64>,
+    pub start_from: Option<NaiveDate>,
+    pub end_until: Option<NaiveDate>,
+    pub status: Option<OfferStatus>,
+    pub available: Option<bool>,
+    pub near: Option<GeoNear>,
+    /// Keywords searched in titles and descriptions, matching offers with any
+    /// of them, most relevant first unless another sort is asked for.
+    pub text: Option<String>,
+    /// Lowercased skills, matched according to `skill_match`.
+    pub skills: Vec<String>,
+    pub skill_match: SkillMatch,
+    /// Also match offers waiting for their scheduled publication.
+    pub include_scheduled: bool,
+}
+
+impl OfferQuery {
+    pub fn has_criteria(&self) -> bool {
+        self.city.is_some()
+            || self.company_id.is_some()
+            || self.domain.is_some()
+            || self.min_salary.is_some()
+            || self.max_salary.is_some()
+            || self.start_from.is_some()
+            || self.end_until.is_some()
+            || self.near.is_some()
+            || self.text.is_some()
+            || !self.skills.is_empty()
+    }
+}
+
+/// Whether an offer must require any or all of the skills searched for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillMatch {
+    #[default]
+    Any,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferSort {
+    Salary,
+    StartDate,
+    Title,
+}
+
+impl OfferSort {
+    pub fn field(&self) -> &'static str {
+        match self {
+            OfferSort::Salary => "salary_monthly_eur",
+            OfferSort::StartDate => "start_date",
+            OfferSort::Title => "title",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone)]
+pub struct PageRequest {
+    pub offset: u64,
+    pub limit: u64,
+    pub sort: Option<OfferSort>,
+    pub order: SortOrder,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OfferPage {
+    pub offers: Vec<Offer>,
+    pub total: u64,
+    pub offset: u64,
+    pub limit: u64,
 }
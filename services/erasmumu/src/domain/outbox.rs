@@ -0,0 +1,27 @@
+use crate::domain::events::OfferEvent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An event recorded alongside the offer write that produced it, waiting to
+/// be relayed to RabbitMQ.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    pub id: String,
+    pub routing_key: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+impl OutboxMessage {
+    pub fn new(event: &OfferEvent) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            routing_key: event.routing_key().to_string(),
+            payload: serde_json::to_value(event).unwrap_or_default(),
+            created_at: Utc::now(),
+            attempts: 0,
+        }
+    }
+}
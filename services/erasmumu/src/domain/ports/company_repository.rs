@@ -0,0 +1,40 @@
+use crate::domain::company::Company;
+use crate::domain::ports::offer_repository::FieldError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CompanyError {
+    #[error("Company not found")]
+    NotFound,
+    #[error("Validation failed on {} field(s)", .0.len())]
+    ValidationFailed(Vec<FieldError>),
+    #[error("Company still has {0} offer(s)")]
+    InUse(u64),
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+}
+
+pub trait CompanyRepository: Send + Sync {
+    fn create(
+        &self,
+        company: &Company,
+    ) -> impl std::future::Future<Output = Result<Company, CompanyError>> + Send;
+
+    fn get_by_id(
+        &self,
+        id: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Company>, CompanyError>> + Send;
+
+    /// Every company, by name.
+    fn list(&self) -> impl std::future::Future<Output = Result<Vec<Company>, CompanyError>> + Send;
+
+    fn update(
+        &self,
+        company: &Company,
+    ) -> impl std::future::Future<Output = Result<Company, CompanyError>> + Send;
+
+    fn delete(
+        &self,
+        id: &str,
+    ) -> impl std::future::Future<Output = Result<(), CompanyError>> + Send;
+}
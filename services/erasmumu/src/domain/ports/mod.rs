@@ -1 +1,6 @@
+pub mod clock;
+pub mod company_repository;
 pub mod offer_repository;
+pub mod outbox_repository;
+pub mod webhook_repository;
+pub mod webhook_sender;
@@ -1,41 +1,171 @@
-use crate::domain::offer::Offer;
+use crate::domain::facets::OfferFacets;
+use crate::domain::history::{ChangeLog, HistoryEntry};
+use crate::domain::idempotency::IdempotencyRecord;
+use crate::domain::offer::{
+    ExpiryReference, Offer, OfferPage, OfferQuery, OfferStatus, PageRequest,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::stream::BoxStream;
+use serde::Serialize;
 use thiserror::Error;
 
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum OfferError {
     #[error("Offer not found")]
     NotFound,
     #[error("Invalid data: {0}")]
     InvalidData(String),
+    #[error("Validation failed on {} field(s)", .0.len())]
+    ValidationFailed(Vec<FieldError>),
+    #[error("Offer has changed, it is now at version {current}")]
+    VersionMismatch { current: u64 },
+    #[error("Cannot move offer from {from} to {to}")]
+    InvalidTransition { from: OfferStatus, to: OfferStatus },
+    #[error("Offer has no seats left, raise its positions to publish it")]
+    NoSeatsLeft,
+    #[error("Offer duplicates offer {existing_id}")]
+    Duplicate { existing_id: String },
+    #[error("Idempotency-Key was already used for a different request")]
+    IdempotencyKeyReused,
     #[error("Database error: {0}")]
     DatabaseError(String),
 }
 
+/// Writes take the events and history entries they produce and store them as
+/// part of the same write, so those exist if and only if the change does.
+///
+/// Listings and `reserve` ignore deleted offers and offers waiting for their
+/// scheduled publication, unless [`OfferQuery::include_scheduled`] says
+/// otherwise; `get_by_id` still returns them.
+///
+/// No two offers, deleted ones aside, may be duplicates of each other (see
+/// [`Offer::duplicates`]): writes that would store one, restoring a deleted
+/// offer included, fail with [`OfferError::Duplicate`].
 pub trait OfferRepository: Send + Sync {
     fn create(
         &self,
         offer: &Offer,
+        log: &ChangeLog,
+    ) -> impl std::future::Future<Output = Result<Offer, OfferError>> + Send;
+
+    /// Creates the offer and stores `record` along with it. Fails with
+    /// [`OfferError::IdempotencyKeyReused`] when a record already holds the
+    /// key, whether or not it has expired.
+    fn create_idempotent(
+        &self,
+        offer: &Offer,
+        log: &ChangeLog,
+        record: &IdempotencyRecord,
     ) -> impl std::future::Future<Output = Result<Offer, OfferError>> + Send;
 
+    /// The record stored for `key`, unless it has expired.
+    fn find_idempotency_record(
+        &self,
+        key: &str,
+    ) -> impl std::future::Future<Output = Result<Option<IdempotencyRecord>, OfferError>> + Send;
+
+    /// Inserts all offers or none of them.
+    fn create_many(
+        &self,
+        offers: &[Offer],
+        log: &ChangeLog,
+    ) -> impl std::future::Future<Output = Result<(), OfferError>> + Send;
+
     fn get_by_id(
         &self,
         id: &str,
     ) -> impl std::future::Future<Output = Result<Option<Offer>, OfferError>> + Send;
 
-    fn list_by_domain(
+    /// A live stored offer other than `offer` itself that it duplicates.
+    fn find_duplicate(
+        &self,
+        offer: &Offer,
+    ) -> impl std::future::Future<Output = Result<Option<Offer>, OfferError>> + Send;
+
+    fn search(
         &self,
-        domain: &str,
+        query: &OfferQuery,
+        page: &PageRequest,
+    ) -> impl std::future::Future<Output = Result<OfferPage, OfferError>> + Send;
+
+    /// Counts offers matching `query` per city, domain, salary bucket and start month.
+    fn facets(
+        &self,
+        query: &OfferQuery,
+    ) -> impl std::future::Future<Output = Result<OfferFacets, OfferError>> + Send;
+
+    /// Streams every offer matching `query` in insertion order, without loading
+    /// them all in memory.
+    fn stream(
+        &self,
+        query: &OfferQuery,
+    ) -> impl std::future::Future<
+        Output = Result<BoxStream<'static, Result<Offer, OfferError>>, OfferError>,
+    > + Send;
+
+    /// Browses every published offer, newest first unless `page.sort` says otherwise.
+    fn list_all(
+        &self,
+        page: &PageRequest,
+    ) -> impl std::future::Future<Output = Result<OfferPage, OfferError>> + Send;
+
+    /// Lists published offers whose `reference` date is strictly before `today`.
+    fn list_past_due(
+        &self,
+        reference: ExpiryReference,
+        today: NaiveDate,
     ) -> impl std::future::Future<Output = Result<Vec<Offer>, OfferError>> + Send;
 
-    fn list_by_city(
+    /// Offers, deleted ones aside, scheduled to go live at or before `now`.
+    fn list_due_for_publication(
         &self,
-        city: &str,
+        now: DateTime<Utc>,
     ) -> impl std::future::Future<Output = Result<Vec<Offer>, OfferError>> + Send;
 
+    /// Atomically takes one seat of a published offer (see [`Offer::take_seat`])
+    /// and records the change. Returns `None` when no published offer has a
+    /// seat left, whether it is missing or already taken.
+    fn reserve(
+        &self,
+        id: &str,
+        actor: Option<&str>,
+    ) -> impl std::future::Future<Output = Result<Option<Offer>, OfferError>> + Send;
+
+    /// Replaces the offer only if the stored one is still at the version just
+    /// before `offer.version`, as set by [`OfferUpdate::new`]; otherwise fails
+    /// with [`OfferError::VersionMismatch`].
+    ///
+    /// [`OfferUpdate::new`]: crate::domain::offer::OfferUpdate::new
     fn update(
         &self,
         offer: &Offer,
+        log: &ChangeLog,
     ) -> impl std::future::Future<Output = Result<Offer, OfferError>> + Send;
 
-    fn delete(&self, id: &str) -> impl std::future::Future<Output = Result<(), OfferError>> + Send;
+    /// History entries of an offer, oldest first.
+    fn history(
+        &self,
+        offer_id: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<HistoryEntry>, OfferError>> + Send;
+
+    /// Permanently removes offers deleted before `before` and returns how many went.
+    fn purge_deleted(
+        &self,
+        before: DateTime<Utc>,
+    ) -> impl std::future::Future<Output = Result<u64, OfferError>> + Send;
 }
@@ -0,0 +1,39 @@
+use crate::domain::outbox::OutboxMessage;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OutboxError {
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+}
+
+pub trait OutboxRepository: Send + Sync {
+    /// Oldest messages not yet relayed, in the order they were recorded.
+    fn pending(
+        &self,
+        limit: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<OutboxMessage>, OutboxError>> + Send;
+
+    fn mark_sent(
+        &self,
+        id: &str,
+    ) -> impl std::future::Future<Output = Result<(), OutboxError>> + Send;
+
+    /// Oldest messages not yet handed to webhook subscriptions, whether or not
+    /// they were relayed.
+    fn pending_fan_out(
+        &self,
+        limit: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<OutboxMessage>, OutboxError>> + Send;
+
+    fn mark_fanned_out(
+        &self,
+        id: &str,
+    ) -> impl std::future::Future<Output = Result<(), OutboxError>> + Send;
+
+    fn record_failure(
+        &self,
+        id: &str,
+        error: &str,
+    ) -> impl std::future::Future<Output = Result<(), OutboxError>> + Send;
+}
@@ -0,0 +1,67 @@
+use crate::domain::ports::offer_repository::FieldError;
+use crate::domain::webhook::{WebhookDelivery, WebhookSubscription};
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("Webhook subscription not found")]
+    NotFound,
+    #[error("Validation failed on {} field(s)", .0.len())]
+    ValidationFailed(Vec<FieldError>),
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+}
+
+pub trait WebhookRepository: Send + Sync {
+    fn create_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> impl std::future::Future<Output = Result<WebhookSubscription, WebhookError>> + Send;
+
+    fn get_subscription(
+        &self,
+        id: &str,
+    ) -> impl std::future::Future<Output = Result<Option<WebhookSubscription>, WebhookError>> + Send;
+
+    /// Every subscription, oldest first.
+    fn list_subscriptions(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<WebhookSubscription>, WebhookError>> + Send;
+
+    fn update_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> impl std::future::Future<Output = Result<WebhookSubscription, WebhookError>> + Send;
+
+    /// Removes the subscription along with its deliveries.
+    fn delete_subscription(
+        &self,
+        id: &str,
+    ) -> impl std::future::Future<Output = Result<(), WebhookError>> + Send;
+
+    fn enqueue(
+        &self,
+        deliveries: &[WebhookDelivery],
+    ) -> impl std::future::Future<Output = Result<(), WebhookError>> + Send;
+
+    /// Pending deliveries whose next attempt is due at `now`, oldest first.
+    fn due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send;
+
+    /// Stores the delivery as it is after an attempt.
+    fn save_delivery(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> impl std::future::Future<Output = Result<(), WebhookError>> + Send;
+
+    /// Deliveries to a subscription, newest first.
+    fn deliveries(
+        &self,
+        subscription_id: &str,
+        limit: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send;
+}
@@ -0,0 +1,11 @@
+/// POSTs webhook deliveries to partner endpoints.
+pub trait WebhookSender: Send + Sync {
+    /// Sends `body` as JSON with `headers` and returns the response status, or
+    /// why the endpoint could not be reached.
+    fn send(
+        &self,
+        url: &str,
+        headers: &[(&'static str, String)],
+        body: Vec<u8>,
+    ) -> impl std::future::Future<Output = Result<u16, String>> + Send;
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Currency every salary is normalized to.
+pub const BASE_CURRENCY: &str = "EUR";
+/// Legal monthly working hours in France (35 hours a week).
+pub const HOURS_PER_MONTH: f64 = 151.67;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SalaryPeriod {
+    Hourly,
+    #[default]
+    Monthly,
+    Yearly,
+}
+
+impl SalaryPeriod {
+    pub const ALL: [SalaryPeriod; 3] = [
+        SalaryPeriod::Hourly,
+        SalaryPeriod::Monthly,
+        SalaryPeriod::Yearly,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SalaryPeriod::Hourly => "hourly",
+            SalaryPeriod::Monthly => "monthly",
+            SalaryPeriod::Yearly => "yearly",
+        }
+    }
+
+    /// Factor turning an amount paid per period into a monthly amount.
+    pub fn per_month(&self) -> f64 {
+        match self {
+            SalaryPeriod::Hourly => HOURS_PER_MONTH,
+            SalaryPeriod::Monthly => 1.0,
+            SalaryPeriod::Yearly => 1.0 / 12.0,
+        }
+    }
+}
+
+/// Value of one unit of each supported currency in euros. The table is static
+/// for the lifetime of the process; stored offers are re-normalized at startup
+/// so that a new table applies to them too.
+#[derive(Debug, Clone)]
+pub struct ExchangeRates {
+    rates: HashMap<String, f64>,
+}
+
+impl Default for ExchangeRates {
+    fn default() -> Self {
+        let rates = [
+            ("EUR", 1.0),
+            ("CHF", 1.06),
+            ("GBP", 1.17),
+            ("USD", 0.92),
+            ("CAD", 0.68),
+            ("SEK", 0.088),
+            ("NOK", 0.086),
+            ("DKK", 0.134),
+            ("PLN", 0.23),
+            ("CZK", 0.04),
+            ("HUF", 0.0025),
+        ];
+        Self {
+            rates: rates
+                .into_iter()
+                .map(|(code, rate)| (code.to_string(), rate))
+                .collect(),
+        }
+    }
+}
+
+impl ExchangeRates {
+    /// Adds or replaces rates on top of the bundled table.
+    pub fn with_overrides(mut self, overrides: ExchangeRates) -> Self {
+        self.rates.extend(overrides.rates);
+        self
+    }
+
+    pub fn rate(&self, currency: &str) -> Option<f64> {
+        self.rates.get(currency).copied()
+    }
+
+    pub fn currencies(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.rates.iter().map(|(code, rate)| (code.as_str(), *rate))
+    }
+
+    /// Monthly amount in euros, or `None` for an unknown currency.
+    pub fn monthly_eur(&self, amount: f64, currency: &str, period: SalaryPeriod) -> Option<f64> {
+        self.rate(currency)
+            .map(|rate| amount * rate * period.per_month())
+    }
+}
+
+impl std::str::FromStr for ExchangeRates {
+    type Err = String;
+
+    /// Parses `CODE=rate` pairs separated by commas, e.g. `CHF=1.06,USD=0.92`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut rates = HashMap::new();
+        for pair in value
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (code, rate) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected CODE=rate, got {}", pair))?;
+            let code = normalize_currency(code);
+            if !is_currency_code(&code) {
+                return Err(format!("invalid currency code: {}", code));
+            }
+            let rate: f64 = rate
+                .trim()
+                .parse()
+                .ok()
+                .filter(|rate: &f64| rate.is_finite() && *rate > 0.0)
+                .ok_or_else(|| format!("invalid rate for {}: {}", code, rate.trim()))?;
+            rates.insert(code, rate);
+        }
+        Ok(Self { rates })
+    }
+}
+
+pub fn normalize_currency(raw: &str) -> String {
+    raw.trim().to_ascii_uppercase()
+}
+
+/// Three ASCII letters, as in ISO 4217.
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase())
+}
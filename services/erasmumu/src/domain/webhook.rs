@@ -0,0 +1,184 @@
+use crate::domain::outbox::OutboxMessage;
+use chrono::{DateTime, SubsecRound, TimeDelta, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// Attempts made at a delivery before giving up on it.
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+/// Wait before the first retry, doubled after every failed attempt.
+pub const FIRST_RETRY_DELAY: TimeDelta = TimeDelta::seconds(30);
+pub const MIN_SECRET_LEN: usize = 16;
+
+pub const EVENT_HEADER: &str = "x-polymove-event";
+pub const DELIVERY_HEADER: &str = "x-polymove-delivery";
+/// `t=<unix seconds>,v1=<hex HMAC-SHA256 of "<t>.<body>" keyed with the secret>`.
+pub const SIGNATURE_HEADER: &str = "x-polymove-signature";
+
+/// A partner endpoint receiving offer events as signed HTTP POSTs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: String,
+    pub url: String,
+    /// Routing keys to deliver, every event when empty.
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Shared with the partner to sign deliveries; never listed back.
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    pub fn wants(&self, routing_key: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| event == routing_key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    /// Gave up after [`MAX_DELIVERY_ATTEMPTS`].
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+/// One POST of a delivery: the response status when the endpoint answered,
+/// or why it could not be reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    pub at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DeliveryAttempt {
+    pub fn succeeded(&self) -> bool {
+        self.status_code
+            .is_some_and(|status| (200..300).contains(&status))
+    }
+}
+
+/// An event to deliver to one subscription, with every attempt made so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub subscription_id: String,
+    /// Id of the outbox message the event comes from, the same for every
+    /// subscription it is delivered to.
+    pub event_id: String,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    #[serde(default)]
+    pub attempts: Vec<DeliveryAttempt>,
+    /// When the next attempt is due, while the delivery is pending. Stored
+    /// with whole seconds so that the RFC 3339 strings compare in order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What deliveries POST, the same bytes being signed.
+#[derive(Serialize)]
+struct DeliveryBody<'a> {
+    id: &'a str,
+    event: &'a str,
+    created_at: DateTime<Utc>,
+    data: &'a serde_json::Value,
+}
+
+impl WebhookDelivery {
+    /// Due at once.
+    pub fn new(
+        subscription: &WebhookSubscription,
+        message: &OutboxMessage,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let now = now.trunc_subsecs(0);
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            subscription_id: subscription.id.clone(),
+            event_id: message.id.clone(),
+            event: message.routing_key.clone(),
+            payload: message.payload.clone(),
+            status: DeliveryStatus::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: Some(now),
+            created_at: now,
+        }
+    }
+
+    pub fn body(&self) -> Vec<u8> {
+        serde_json::to_vec(&DeliveryBody {
+            id: &self.event_id,
+            event: &self.event,
+            created_at: self.created_at,
+            data: &self.payload,
+        })
+        .unwrap_or_default()
+    }
+
+    /// Adds the attempt and either settles the delivery or schedules a retry
+    /// after [`retry_delay`].
+    pub fn record(&mut self, attempt: DeliveryAttempt) {
+        let at = attempt.at;
+        let succeeded = attempt.succeeded();
+        self.attempts.push(attempt);
+
+        let attempts = self.attempts.len() as u32;
+        (self.status, self.next_attempt_at) = if succeeded {
+            (DeliveryStatus::Delivered, None)
+        } else if attempts >= MAX_DELIVERY_ATTEMPTS {
+            (DeliveryStatus::Failed, None)
+        } else {
+            (
+                DeliveryStatus::Pending,
+                Some((at + retry_delay(attempts)).trunc_subsecs(0)),
+            )
+        };
+    }
+
+    /// Gives up without sending, e.g. once the subscription is gone.
+    pub fn abandon(&mut self, at: DateTime<Utc>, reason: &str) {
+        self.attempts.push(DeliveryAttempt {
+            at,
+            status_code: None,
+            error: Some(reason.to_string()),
+        });
+        self.status = DeliveryStatus::Failed;
+        self.next_attempt_at = None;
+    }
+}
+
+/// Wait after the `attempts`-th failed attempt: [`FIRST_RETRY_DELAY`], then
+/// twice as long every time.
+pub fn retry_delay(attempts: u32) -> TimeDelta {
+    FIRST_RETRY_DELAY * 2_i32.pow(attempts.saturating_sub(1).min(16))
+}
+
+/// Value of [`SIGNATURE_HEADER`] for `body` sent at `timestamp`.
+pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
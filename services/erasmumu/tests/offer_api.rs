@@ -0,0 +1,204 @@
+use erasmumu::adapters::http::router;
+use erasmumu::adapters::persistence::memory::company_repository::InMemoryCompanyRepository;
+use erasmumu::adapters::persistence::memory::offer_repository::InMemoryOfferRepository;
+use erasmumu::application::company_service::CompanyService;
+use erasmumu::application::offer_service::{CreateOfferParams, OfferService};
+use erasmumu::domain::offer::Offer;
+use erasmumu::domain::salary::ExchangeRates;
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH};
+use std::sync::Arc;
+
+type Service = OfferService<InMemoryOfferRepository, InMemoryCompanyRepository>;
+
+/// Serves the API over in-memory repositories and returns its base URL along
+/// with the service behind it, to set offers up directly.
+async fn api() -> (String, Arc<Service>) {
+    let companies = Arc::new(InMemoryCompanyRepository::new());
+    let service = Arc::new(OfferService::new(
+        InMemoryOfferRepository::new(),
+        companies.clone(),
+        ExchangeRates::default(),
+    ));
+    let app = router(
+        service.clone(),
+        Arc::new(CompanyService::new(companies, service.clone())),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}", addr), service)
+}
+
+async fn create(service: &Service, title: &str, city: &str) -> Offer {
+    service
+        .create_offer(
+            CreateOfferParams {
+                title: title.to_string(),
+                link: format!(
+                    "https://example.com/offers/{}",
+                    title.to_lowercase().replace(' ', "-")
+                ),
+                city: city.to_string(),
+                domain: "AI".to_string(),
+                description: None,
+                salary: 1200.0,
+                currency: None,
+                salary_period: None,
+                start_date: "2026-06-01".to_string(),
+                end_date: "2026-09-30".to_string(),
+                status: None,
+                positions: None,
+                company_id: None,
+                publish_at: None,
+                skills: vec!["Rust".to_string(), "SQL".to_string()],
+                latitude: None,
+                longitude: None,
+            },
+            None,
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn export_streams_matching_offers_as_csv_or_ndjson() {
+    let (url, service) = api().await;
+    let lyon = create(&service, "Data intern", "Lyon").await;
+    create(&service, "Paris intern", "Paris").await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{url}/offers/export?format=csv&city=Lyon"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/csv; charset=utf-8");
+    let csv = response.text().await.unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("id,title,link,city,domain,description,skills,"));
+    assert!(lines[1].starts_with(&format!("{},Data intern,", lyon.id)));
+    assert!(lines[1].contains(",rust;sql,"));
+
+    let ndjson = client
+        .get(format!("{url}/offers/export?format=ndjson"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    let titles: Vec<String> = ndjson
+        .lines()
+        .map(|line| serde_json::from_str::<Offer>(line).unwrap().title)
+        .collect();
+    assert_eq!(titles, ["Data intern", "Paris intern"]);
+
+    let invalid = client
+        .get(format!(
+            "{url}/offers/export?min_salary=2000&max_salary=1000"
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn updates_with_a_stale_etag_are_refused() {
+    let (url, service) = api().await;
+    let offer = create(&service, "Contended", "Lyon").await;
+    let client = reqwest::Client::new();
+    let offer_url = format!("{url}/offer/{}", offer.id);
+    let put = |etag: &str, salary: f64| {
+        client
+            .put(&offer_url)
+            .header(IF_MATCH, etag)
+            .header(CONTENT_TYPE, "application/json")
+            .body(format!("{{\"salary\": {salary}}}"))
+            .send()
+    };
+
+    let read = client.get(&offer_url).send().await.unwrap();
+    let etag = read.headers()[ETAG].to_str().unwrap().to_string();
+    assert_eq!(etag, "\"1\"");
+
+    let updated = put(&etag, 1300.0).await.unwrap();
+    assert_eq!(updated.status(), StatusCode::OK);
+    assert_eq!(updated.headers()[ETAG], "\"2\"");
+
+    let stale = put(&etag, 1400.0).await.unwrap();
+    assert_eq!(stale.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(service.get_offer(&offer.id).await.unwrap().salary, 1300.0);
+
+    let any = put("*", 1400.0).await.unwrap();
+    assert_eq!(any.status(), StatusCode::OK);
+    assert_eq!(any.headers()[ETAG], "\"3\"");
+}
+
+#[tokio::test]
+async fn csv_imports_read_skills_that_look_like_numbers() {
+    let (url, service) = api().await;
+    let csv = "title,link,city,domain,salary,start_date,end_date,skills\n\
+        Telecom intern,https://example.com/5g,Lyon,Telecom,1200,2026-06-01,2026-09-30,5\n\
+        Flags intern,https://example.com/flags,Lyon,AI,1200,2026-06-01,2026-09-30,true\n\
+        Mixed intern,https://example.com/mixed,Lyon,AI,1200,2026-06-01,2026-09-30,1.5;Rust\n";
+
+    let response = reqwest::Client::new()
+        .post(format!("{url}/offers/import"))
+        .header(CONTENT_TYPE, "text/csv")
+        .body(csv)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let report: serde_json::Value = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(report["errors"], serde_json::json!([]));
+
+    let mut skills = Vec::new();
+    for accepted in report["accepted"].as_array().unwrap() {
+        let offer = service
+            .get_offer(accepted["id"].as_str().unwrap())
+            .await
+            .unwrap();
+        skills.push(offer.skills);
+    }
+    assert_eq!(skills, [vec!["5"], vec!["true"], vec!["1.5", "rust"]]);
+}
+
+#[tokio::test]
+async fn list_facets_and_export_read_the_same_filters() {
+    let (url, service) = api().await;
+    let lyon = create(&service, "Data intern", "Lyon").await;
+    create(&service, "Paris intern", "Paris").await;
+    let client = reqwest::Client::new();
+    let filters = "city=Lyon&min_salary=1000&max_salary=1500.5&available=true";
+    let get = |path: String| {
+        let client = client.clone();
+        async move {
+            let response = client.get(path).send().await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            serde_json::from_str::<serde_json::Value>(&response.text().await.unwrap()).unwrap()
+        }
+    };
+
+    let page = get(format!("{url}/offer?{filters}&limit=5")).await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["offers"][0]["id"], lyon.id.as_str());
+
+    let facets = get(format!("{url}/offers/facets?{filters}")).await;
+    assert_eq!(facets["total"], 1);
+    assert_eq!(facets["cities"][0]["value"], "Lyon");
+
+    let export = get(format!("{url}/offers/export?format=ndjson&{filters}")).await;
+    assert_eq!(export["id"], lyon.id.as_str());
+
+    let invalid = client
+        .get(format!("{url}/offer?min_salary=lots"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,1153 @@
+//! Assertions every `OfferRepository` must satisfy, run against the in-memory
+//! implementation and a MongoDB replica set at `MONGODB_TEST_URI`. Without
+//! that variable, a local single-node replica set is used when one answers.
+//! Each Mongo test works in a database of its own and drops it when it passes.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use erasmumu::adapters::persistence::memory::offer_repository::InMemoryOfferRepository;
+use erasmumu::adapters::persistence::mongo::offer_repository::MongoOfferRepository;
+use erasmumu::adapters::persistence::mongo::outbox_repository::MongoOutboxRepository;
+use erasmumu::domain::facets::FacetCount;
+use erasmumu::domain::geo::{GeoNear, city_coordinates};
+use erasmumu::domain::history::{ChangeLog, HistoryAction};
+use erasmumu::domain::idempotency::{IDEMPOTENCY_KEY_TTL, IdempotencyRecord};
+use erasmumu::domain::offer::{
+    ExpiryReference, Offer, OfferQuery, OfferSort, OfferStatus, OfferUpdate, PageRequest,
+    SkillMatch, SortOrder,
+};
+use erasmumu::domain::ports::offer_repository::{OfferError, OfferRepository};
+use erasmumu::domain::ports::outbox_repository::OutboxRepository;
+use erasmumu::domain::salary::SalaryPeriod;
+use futures_util::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::options::ClientOptions;
+use mongodb::{Client, Database};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+fn date(raw: &str) -> NaiveDate {
+    raw.parse().unwrap()
+}
+
+/// Offers with different titles have different links, so they do not duplicate each other.
+fn link(title: &str) -> String {
+    format!(
+        "https://example.com/offers/{}",
+        title.to_lowercase().replace(' ', "-")
+    )
+}
+
+fn offer(title: &str) -> Offer {
+    Offer {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: title.to_string(),
+        link: link(title),
+        city: "Lyon".to_string(),
+        domain: "AI".to_string(),
+        description: None,
+        skills: Vec::new(),
+        location: city_coordinates("Lyon"),
+        salary: 1200.0,
+        currency: "EUR".to_string(),
+        salary_period: SalaryPeriod::Monthly,
+        salary_monthly_eur: 1200.0,
+        start_date: date("2026-06-01"),
+        end_date: date("2026-09-30"),
+        status: OfferStatus::Published,
+        company_id: None,
+        positions: 1,
+        remaining: 1,
+        available: true,
+        deleted_at: None,
+        publish_at: None,
+        version: 1,
+        distance_km: None,
+    }
+}
+
+fn page(offset: u64, limit: u64) -> PageRequest {
+    PageRequest {
+        offset,
+        limit,
+        sort: None,
+        order: SortOrder::Asc,
+    }
+}
+
+async fn store<R: OfferRepository>(repository: &R, offer: Offer) -> Offer {
+    repository
+        .create(&offer, &ChangeLog::created(&offer, None))
+        .await
+        .unwrap()
+}
+
+/// Applies `change` to the stored offer the way the service does.
+async fn modify<R: OfferRepository>(
+    repository: &R,
+    id: &str,
+    change: impl FnOnce(&mut Offer),
+) -> Result<Offer, OfferError> {
+    let existing = repository.get_by_id(id).await?.unwrap();
+    let mut next = existing.clone();
+    change(&mut next);
+    let update = OfferUpdate::new(&existing, next);
+    repository
+        .update(&update.offer, &ChangeLog::updated(&update, None))
+        .await
+}
+
+fn titles(offers: &[Offer]) -> Vec<&str> {
+    offers.iter().map(|offer| offer.title.as_str()).collect()
+}
+
+async fn create_then_get_by_id<R: OfferRepository>(repository: &R) {
+    let created = store(repository, offer("Data intern")).await;
+
+    let found = repository.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(found.title, "Data intern");
+    assert_eq!(found.start_date, date("2026-06-01"));
+    assert_eq!(found.location, city_coordinates("Lyon"));
+    assert_eq!(found.version, 1);
+    assert!(repository.get_by_id("missing").await.unwrap().is_none());
+}
+
+async fn create_many_inserts_every_offer<R: OfferRepository>(repository: &R) {
+    let offers = vec![offer("First"), offer("Second")];
+    repository
+        .create_many(&offers, &ChangeLog::default())
+        .await
+        .unwrap();
+
+    for offer in &offers {
+        assert!(repository.get_by_id(&offer.id).await.unwrap().is_some());
+    }
+}
+
+async fn search_applies_every_criterion<R: OfferRepository>(repository: &R) {
+    store(repository, offer("Lyon AI")).await;
+    store(
+        repository,
+        Offer {
+            city: "Paris".to_string(),
+            location: city_coordinates("Paris"),
+            ..offer("Paris AI")
+        },
+    )
+    .await;
+    store(
+        repository,
+        Offer {
+            domain: "Web".to_string(),
+            skills: vec!["rust".to_string(), "sql".to_string()],
+            salary: 2000.0,
+            salary_monthly_eur: 2000.0,
+            company_id: Some("acme".to_string()),
+            ..offer("Lyon Web")
+        },
+    )
+    .await;
+    let mut draft = offer("Lyon draft");
+    draft.set_status(OfferStatus::Draft);
+    store(repository, draft).await;
+
+    let search = |query: OfferQuery| async move {
+        let mut found = repository.search(&query, &page(0, 10)).await.unwrap();
+        found.offers.sort_by(|a, b| a.title.cmp(&b.title));
+        found.offers
+    };
+
+    let in_lyon = search(OfferQuery {
+        city: Some("Lyon".to_string()),
+        status: Some(OfferStatus::Published),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(titles(&in_lyon), ["Lyon AI", "Lyon Web"]);
+
+    let drafts = search(OfferQuery {
+        status: Some(OfferStatus::Draft),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(titles(&drafts), ["Lyon draft"]);
+
+    let unavailable = search(OfferQuery {
+        available: Some(false),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(titles(&unavailable), ["Lyon draft"]);
+
+    let well_paid = search(OfferQuery {
+        min_salary: Some(1500.0),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(titles(&well_paid), ["Lyon Web"]);
+
+    let by_domain_and_company = search(OfferQuery {
+        domain: Some("Web".to_string()),
+        company_id: Some("acme".to_string()),
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(titles(&by_domain_and_company), ["Lyon Web"]);
+
+    let any_skill = search(OfferQuery {
+        skills: vec!["sql".to_string(), "go".to_string()],
+        skill_match: SkillMatch::Any,
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(titles(&any_skill), ["Lyon Web"]);
+
+    let all_skills = search(OfferQuery {
+        skills: vec!["sql".to_string(), "go".to_string()],
+        skill_match: SkillMatch::All,
+        ..Default::default()
+    })
+    .await;
+    assert!(all_skills.is_empty());
+
+    let by_dates = search(OfferQuery {
+        start_from: Some(date("2026-06-01")),
+        end_until: Some(date("2026-09-29")),
+        ..Default::default()
+    })
+    .await;
+    assert!(by_dates.is_empty());
+}
+
+async fn search_pages_newest_first<R: OfferRepository>(repository: &R) {
+    for title in ["1", "2", "3", "4", "5"] {
+        store(repository, offer(title)).await;
+    }
+    let query = OfferQuery::default();
+
+    let first = repository.search(&query, &page(0, 2)).await.unwrap();
+    assert_eq!(titles(&first.offers), ["5", "4"]);
+    assert_eq!(first.total, 5);
+
+    let last = repository.search(&query, &page(4, 2)).await.unwrap();
+    assert_eq!(titles(&last.offers), ["1"]);
+    assert_eq!(last.total, 5);
+}
+
+async fn search_sorts_by_monthly_salary<R: OfferRepository>(repository: &R) {
+    for (title, monthly_eur) in [("mid", 1200.0), ("high", 1500.0), ("low", 900.0)] {
+        store(
+            repository,
+            Offer {
+                salary_monthly_eur: monthly_eur,
+                ..offer(title)
+            },
+        )
+        .await;
+    }
+    let query = OfferQuery::default();
+
+    let ascending = PageRequest {
+        sort: Some(OfferSort::Salary),
+        ..page(0, 10)
+    };
+    let found = repository.search(&query, &ascending).await.unwrap();
+    assert_eq!(titles(&found.offers), ["low", "mid", "high"]);
+
+    let descending = PageRequest {
+        order: SortOrder::Desc,
+        ..ascending
+    };
+    let found = repository.search(&query, &descending).await.unwrap();
+    assert_eq!(titles(&found.offers), ["high", "mid", "low"]);
+}
+
+async fn search_near_returns_nearest_first_with_distance<R: OfferRepository>(repository: &R) {
+    store(
+        repository,
+        Offer {
+            city: "Paris".to_string(),
+            location: city_coordinates("Paris"),
+            ..offer("Paris")
+        },
+    )
+    .await;
+    store(repository, offer("Lyon")).await;
+    store(
+        repository,
+        Offer {
+            location: None,
+            ..offer("Nowhere")
+        },
+    )
+    .await;
+
+    let near = |radius_km| OfferQuery {
+        near: Some(GeoNear {
+            center: city_coordinates("Lyon").unwrap(),
+            radius_km,
+        }),
+        ..Default::default()
+    };
+
+    let close = repository.search(&near(50.0), &page(0, 10)).await.unwrap();
+    assert_eq!(titles(&close.offers), ["Lyon"]);
+    assert_eq!(close.total, 1);
+    assert!(close.offers[0].distance_km.unwrap() < 1.0);
+
+    let wide = repository.search(&near(500.0), &page(0, 10)).await.unwrap();
+    assert_eq!(titles(&wide.offers), ["Lyon", "Paris"]);
+    assert_eq!(wide.total, 2);
+    let to_paris = wide.offers[1].distance_km.unwrap();
+    assert!((380.0..400.0).contains(&to_paris), "{to_paris}");
+}
+
+async fn search_text_ranks_by_relevance<R: OfferRepository>(repository: &R) {
+    store(repository, offer("Computer Vision Intern")).await;
+    store(
+        repository,
+        Offer {
+            description: Some("Object detection and vision models.".to_string()),
+            ..offer("Research Intern")
+        },
+    )
+    .await;
+    store(
+        repository,
+        Offer {
+            description: Some("Payroll and accounting.".to_string()),
+            ..offer("Finance Intern")
+        },
+    )
+    .await;
+
+    let query = OfferQuery {
+        text: Some("computer vision".to_string()),
+        ..Default::default()
+    };
+    let found = repository.search(&query, &page(0, 10)).await.unwrap();
+    assert_eq!(
+        titles(&found.offers),
+        ["Computer Vision Intern", "Research Intern"]
+    );
+    assert_eq!(found.total, 2);
+}
+
+async fn deleted_offers_are_hidden_but_readable<R: OfferRepository>(repository: &R) {
+    let kept = store(repository, offer("Kept")).await;
+    let gone = store(repository, offer("Gone")).await;
+    modify(repository, &gone.id, |offer| offer.mark_deleted(Utc::now()))
+        .await
+        .unwrap();
+
+    let found = repository
+        .search(&OfferQuery::default(), &page(0, 10))
+        .await
+        .unwrap();
+    assert_eq!(titles(&found.offers), ["Kept"]);
+    let all = repository.list_all(&page(0, 10)).await.unwrap();
+    assert_eq!(titles(&all.offers), ["Kept"]);
+    let streamed: Vec<Offer> = repository
+        .stream(&OfferQuery::default())
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(titles(&streamed), ["Kept"]);
+
+    assert!(
+        repository
+            .get_by_id(&gone.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_deleted()
+    );
+    assert!(repository.reserve(&gone.id, None).await.unwrap().is_none());
+    assert!(
+        !repository
+            .get_by_id(&kept.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_deleted()
+    );
+}
+
+async fn stream_yields_matches_in_insertion_order<R: OfferRepository>(repository: &R) {
+    for title in ["a", "b", "c"] {
+        store(repository, offer(title)).await;
+    }
+    store(
+        repository,
+        Offer {
+            domain: "Web".to_string(),
+            ..offer("web")
+        },
+    )
+    .await;
+
+    let query = OfferQuery {
+        domain: Some("AI".to_string()),
+        ..Default::default()
+    };
+    let streamed: Vec<Offer> = repository
+        .stream(&query)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(titles(&streamed), ["a", "b", "c"]);
+}
+
+async fn list_all_only_returns_published_offers<R: OfferRepository>(repository: &R) {
+    store(repository, offer("Published")).await;
+    let mut filled = offer("Filled");
+    filled.set_status(OfferStatus::Filled);
+    store(repository, filled).await;
+
+    let all = repository.list_all(&page(0, 10)).await.unwrap();
+    assert_eq!(titles(&all.offers), ["Published"]);
+    assert_eq!(all.total, 1);
+}
+
+async fn list_past_due_compares_the_reference_date<R: OfferRepository>(repository: &R) {
+    store(
+        repository,
+        Offer {
+            start_date: date("2025-11-01"),
+            end_date: date("2026-01-10"),
+            ..offer("Ended")
+        },
+    )
+    .await;
+    store(repository, offer("Upcoming")).await;
+    let mut draft = Offer {
+        start_date: date("2025-11-01"),
+        end_date: date("2026-01-10"),
+        ..offer("Ended draft")
+    };
+    draft.set_status(OfferStatus::Draft);
+    store(repository, draft).await;
+
+    let by_end = repository
+        .list_past_due(ExpiryReference::EndDate, date("2026-02-01"))
+        .await
+        .unwrap();
+    assert_eq!(titles(&by_end), ["Ended"]);
+
+    let by_start = repository
+        .list_past_due(ExpiryReference::StartDate, date("2026-06-01"))
+        .await
+        .unwrap();
+    assert_eq!(titles(&by_start), ["Ended"]);
+}
+
+async fn reserve_takes_seats_until_filled<R: OfferRepository>(repository: &R) {
+    let created = store(
+        repository,
+        Offer {
+            positions: 2,
+            remaining: 2,
+            ..offer("Two seats")
+        },
+    )
+    .await;
+
+    let first = repository
+        .reserve(&created.id, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.remaining, 1);
+    assert_eq!(first.status, OfferStatus::Published);
+
+    let second = repository
+        .reserve(&created.id, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.remaining, 0);
+    assert_eq!(second.status, OfferStatus::Filled);
+    assert!(!second.available);
+
+    assert!(
+        repository
+            .reserve(&created.id, None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(repository.reserve("missing", None).await.unwrap().is_none());
+
+    let stored = repository.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(stored.remaining, 0);
+    assert_eq!(stored.status, OfferStatus::Filled);
+    assert_eq!(stored.version, 3);
+}
+
+async fn concurrent_reservations_never_overbook<R: OfferRepository>(repository: &R) {
+    let created = store(
+        repository,
+        Offer {
+            positions: 3,
+            remaining: 3,
+            ..offer("Contended seats")
+        },
+    )
+    .await;
+
+    let attempts = (0..10).map(|_| repository.reserve(&created.id, None));
+    let reserved: Vec<Offer> = futures_util::future::join_all(attempts)
+        .await
+        .into_iter()
+        .filter_map(|result| result.unwrap())
+        .collect();
+
+    let mut remaining: Vec<u32> = reserved.iter().map(|offer| offer.remaining).collect();
+    remaining.sort();
+    assert_eq!(remaining, [0, 1, 2]);
+    let stored = repository.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(stored.remaining, 0);
+    assert_eq!(stored.status, OfferStatus::Filled);
+    assert_eq!(stored.version, 4);
+}
+
+async fn scheduled_offers_stay_hidden_until_due<R: OfferRepository>(repository: &R) {
+    let at = |raw: &str| raw.parse::<DateTime<Utc>>().unwrap();
+    let mut scheduled = offer("Scheduled");
+    scheduled.schedule(at("2026-05-01T08:00:00Z"));
+    let scheduled = store(repository, scheduled).await;
+    let mut deleted = offer("Scheduled then deleted");
+    deleted.schedule(at("2026-05-01T08:00:00Z"));
+    let deleted = store(repository, deleted).await;
+    modify(repository, &deleted.id, |offer| {
+        offer.mark_deleted(at("2026-04-01T00:00:00Z"))
+    })
+    .await
+    .unwrap();
+    store(repository, offer("Live")).await;
+
+    let listed = repository
+        .search(&OfferQuery::default(), &page(0, 10))
+        .await
+        .unwrap();
+    assert_eq!(titles(&listed.offers), ["Live"]);
+    let browsed = repository.list_all(&page(0, 10)).await.unwrap();
+    assert_eq!(titles(&browsed.offers), ["Live"]);
+    let with_scheduled = OfferQuery {
+        include_scheduled: true,
+        ..Default::default()
+    };
+    let all = repository
+        .search(&with_scheduled, &page(0, 10))
+        .await
+        .unwrap();
+    assert_eq!(titles(&all.offers), ["Live", "Scheduled"]);
+    assert!(
+        repository
+            .reserve(&scheduled.id, None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(repository.get_by_id(&scheduled.id).await.unwrap().is_some());
+
+    let early = repository
+        .list_due_for_publication(at("2026-05-01T07:59:59Z"))
+        .await
+        .unwrap();
+    assert!(early.is_empty());
+    let due = repository
+        .list_due_for_publication(at("2026-05-01T08:00:00Z"))
+        .await
+        .unwrap();
+    assert_eq!(titles(&due), ["Scheduled"]);
+
+    modify(repository, &scheduled.id, |offer| offer.publish_at = None)
+        .await
+        .unwrap();
+    let listed = repository
+        .search(&OfferQuery::default(), &page(0, 10))
+        .await
+        .unwrap();
+    assert_eq!(titles(&listed.offers), ["Live", "Scheduled"]);
+}
+
+async fn update_requires_the_version_it_read<R: OfferRepository>(repository: &R) {
+    let created = store(repository, offer("Versioned")).await;
+
+    let updated = modify(repository, &created.id, |offer| offer.salary = 1300.0)
+        .await
+        .unwrap();
+    assert_eq!(updated.version, 2);
+
+    let mut stale = created.clone();
+    stale.salary = 1400.0;
+    let stale = OfferUpdate::new(&created, stale);
+    let conflict = repository
+        .update(&stale.offer, &ChangeLog::updated(&stale, None))
+        .await;
+    assert!(matches!(
+        conflict,
+        Err(OfferError::VersionMismatch { current: 2 })
+    ));
+
+    let stored = repository.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(stored.salary, 1300.0);
+    assert_eq!(stored.version, 2);
+
+    let unknown = OfferUpdate::new(&offer("Unknown"), offer("Unknown"));
+    let missing = repository
+        .update(&unknown.offer, &ChangeLog::updated(&unknown, None))
+        .await;
+    assert!(matches!(missing, Err(OfferError::NotFound)));
+}
+
+async fn concurrent_updates_let_one_writer_win<R: OfferRepository>(repository: &R) {
+    let created = store(repository, offer("Contended edits")).await;
+
+    let updates: Vec<OfferUpdate> = (0..5)
+        .map(|index| {
+            let mut edited = created.clone();
+            edited.salary = 1300.0 + index as f64;
+            OfferUpdate::new(&created, edited)
+        })
+        .collect();
+    let logs: Vec<ChangeLog> = updates
+        .iter()
+        .map(|update| ChangeLog::updated(update, None))
+        .collect();
+    let results = futures_util::future::join_all(
+        updates
+            .iter()
+            .zip(&logs)
+            .map(|(update, log)| repository.update(&update.offer, log)),
+    )
+    .await;
+
+    let written: Vec<&Offer> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+    assert_eq!(written.len(), 1);
+    assert!(
+        results
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .all(|e| matches!(e, OfferError::VersionMismatch { current: 2 }))
+    );
+    let stored = repository.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(stored.salary, written[0].salary);
+    assert_eq!(stored.version, 2);
+}
+
+async fn history_lists_changes_oldest_first<R: OfferRepository>(repository: &R) {
+    let created = store(repository, offer("Tracked")).await;
+    modify(repository, &created.id, |offer| {
+        offer.title = "Renamed".to_string()
+    })
+    .await
+    .unwrap();
+
+    let history = repository.history(&created.id).await.unwrap();
+    let actions: Vec<HistoryAction> = history.iter().map(|entry| entry.action).collect();
+    assert_eq!(actions, [HistoryAction::Created, HistoryAction::Updated]);
+    let rename = &history[1].changes;
+    assert_eq!(rename.len(), 1);
+    assert_eq!(rename[0].field, "title");
+    assert_eq!(rename[0].to, "Renamed");
+
+    assert!(repository.history("missing").await.unwrap().is_empty());
+}
+
+async fn purge_deleted_removes_old_tombstones<R: OfferRepository>(repository: &R) {
+    let at = |raw: &str| raw.parse::<DateTime<Utc>>().unwrap();
+    let old = store(repository, offer("Old")).await;
+    let recent = store(repository, offer("Recent")).await;
+    let live = store(repository, offer("Live")).await;
+    modify(repository, &old.id, |offer| {
+        offer.mark_deleted(at("2026-01-01T00:00:00Z"))
+    })
+    .await
+    .unwrap();
+    modify(repository, &recent.id, |offer| {
+        offer.mark_deleted(at("2026-03-01T00:00:00Z"))
+    })
+    .await
+    .unwrap();
+
+    let purged = repository
+        .purge_deleted(at("2026-02-01T00:00:00Z"))
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert!(repository.get_by_id(&old.id).await.unwrap().is_none());
+    assert!(repository.get_by_id(&recent.id).await.unwrap().is_some());
+    assert!(repository.get_by_id(&live.id).await.unwrap().is_some());
+}
+
+async fn facets_count_matching_offers<R: OfferRepository>(repository: &R) {
+    store(repository, offer("Lyon AI")).await;
+    store(
+        repository,
+        Offer {
+            domain: "Web".to_string(),
+            salary_monthly_eur: 2000.0,
+            start_date: date("2026-07-01"),
+            ..offer("Lyon Web")
+        },
+    )
+    .await;
+    store(
+        repository,
+        Offer {
+            city: "Paris".to_string(),
+            salary_monthly_eur: 3500.0,
+            ..offer("Paris AI")
+        },
+    )
+    .await;
+    let mut draft = offer("Draft");
+    draft.set_status(OfferStatus::Draft);
+    store(repository, draft).await;
+    let gone = store(repository, offer("Gone")).await;
+    modify(repository, &gone.id, |offer| offer.mark_deleted(Utc::now()))
+        .await
+        .unwrap();
+
+    let published = OfferQuery {
+        status: Some(OfferStatus::Published),
+        ..Default::default()
+    };
+    let facets = repository.facets(&published).await.unwrap();
+    assert_eq!(facets.total, 3);
+    let counts = |facet: &[FacetCount]| -> Vec<(String, u64)> {
+        facet
+            .iter()
+            .map(|count| (count.value.clone(), count.count))
+            .collect()
+    };
+    assert_eq!(
+        counts(&facets.cities),
+        [("Lyon".to_string(), 2), ("Paris".to_string(), 1)]
+    );
+    assert_eq!(
+        counts(&facets.domains),
+        [("AI".to_string(), 2), ("Web".to_string(), 1)]
+    );
+    assert_eq!(
+        counts(&facets.start_months),
+        [("2026-06".to_string(), 2), ("2026-07".to_string(), 1)]
+    );
+    let salaries: Vec<(f64, u64)> = facets
+        .salaries
+        .iter()
+        .map(|bucket| (bucket.min, bucket.count))
+        .collect();
+    assert_eq!(
+        salaries,
+        [
+            (0.0, 0),
+            (500.0, 0),
+            (1000.0, 1),
+            (1500.0, 0),
+            (2000.0, 1),
+            (2500.0, 0),
+            (3000.0, 1)
+        ]
+    );
+
+    let in_paris = repository
+        .facets(&OfferQuery {
+            city: Some("Paris".to_string()),
+            ..published
+        })
+        .await
+        .unwrap();
+    assert_eq!(in_paris.total, 1);
+    assert_eq!(counts(&in_paris.domains), [("AI".to_string(), 1)]);
+
+    let nothing = repository
+        .facets(&OfferQuery {
+            city: Some("Nowhere".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(nothing.total, 0);
+    assert!(nothing.cities.is_empty());
+    assert_eq!(nothing.salaries.len(), 7);
+}
+
+fn duplicate_of(result: Result<impl std::fmt::Debug, OfferError>) -> String {
+    match result {
+        Err(OfferError::Duplicate { existing_id }) => existing_id,
+        other => panic!("expected a duplicate, got {other:?}"),
+    }
+}
+
+async fn duplicates_are_rejected<R: OfferRepository>(repository: &R) {
+    let original = store(
+        repository,
+        Offer {
+            company_id: Some("acme".to_string()),
+            ..offer("Data intern")
+        },
+    )
+    .await;
+
+    let same_link = Offer {
+        link: original.link.clone(),
+        ..offer("Other title")
+    };
+    let created = repository
+        .create(&same_link, &ChangeLog::created(&same_link, None))
+        .await;
+    assert_eq!(duplicate_of(created), original.id);
+
+    let same_posting = Offer {
+        city: "LYON".to_string(),
+        company_id: Some("acme".to_string()),
+        ..offer("DATA INTERN")
+    };
+    let created = repository
+        .create(&same_posting, &ChangeLog::created(&same_posting, None))
+        .await;
+    assert_eq!(duplicate_of(created), original.id);
+
+    // Another company may post an internship with the same title in the same city.
+    let other_company = store(
+        repository,
+        Offer {
+            link: link("Data intern at Globex"),
+            company_id: Some("globex".to_string()),
+            ..offer("Data intern")
+        },
+    )
+    .await;
+    let moved = modify(repository, &other_company.id, |offer| {
+        offer.link = original.link.clone()
+    })
+    .await;
+    assert_eq!(duplicate_of(moved), original.id);
+
+    let batch = vec![offer("First of batch"), offer("First of batch")];
+    let created = repository.create_many(&batch, &ChangeLog::default()).await;
+    duplicate_of(created);
+    assert!(repository.get_by_id(&batch[0].id).await.unwrap().is_none());
+
+    // Offers of no known company only clash on their link.
+    let unknown = store(
+        repository,
+        Offer {
+            link: link("Data intern somewhere"),
+            ..offer("Data intern")
+        },
+    )
+    .await;
+    store(
+        repository,
+        Offer {
+            link: link("Data intern elsewhere"),
+            ..offer("DATA INTERN")
+        },
+    )
+    .await;
+    assert!(repository.find_duplicate(&unknown).await.unwrap().is_none());
+
+    // Deleted offers no longer count, but come back only if no live offer
+    // took their place meanwhile.
+    modify(repository, &original.id, |offer| {
+        offer.mark_deleted(Utc::now())
+    })
+    .await
+    .unwrap();
+    assert!(
+        repository
+            .find_duplicate(&same_link)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let reposted = store(repository, same_posting).await;
+    let restored = modify(repository, &original.id, |offer| offer.restore()).await;
+    assert_eq!(duplicate_of(restored), reposted.id);
+}
+
+async fn idempotent_creation_keeps_its_record<R: OfferRepository>(repository: &R) {
+    let first = offer("Idempotent");
+    let record = IdempotencyRecord::new("key-1", "request", &first, Utc::now());
+    repository
+        .create_idempotent(&first, &ChangeLog::created(&first, None), &record)
+        .await
+        .unwrap();
+
+    let found = repository
+        .find_idempotency_record("key-1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.offer.id, first.id);
+    assert_eq!(found.request, "request");
+    assert!(
+        repository
+            .find_idempotency_record("key-2")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let second = offer("Reusing the key");
+    let reused = repository
+        .create_idempotent(
+            &second,
+            &ChangeLog::created(&second, None),
+            &IdempotencyRecord::new("key-1", "other request", &second, Utc::now()),
+        )
+        .await;
+    assert!(matches!(reused, Err(OfferError::IdempotencyKeyReused)));
+    assert!(repository.get_by_id(&second.id).await.unwrap().is_none());
+
+    let old = offer("Old key");
+    let expired =
+        IdempotencyRecord::new("key-3", "request", &old, Utc::now() - IDEMPOTENCY_KEY_TTL);
+    repository
+        .create_idempotent(&old, &ChangeLog::created(&old, None), &expired)
+        .await
+        .unwrap();
+    assert!(
+        repository
+            .find_idempotency_record("key-3")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+const DEFAULT_MONGODB_TEST_URI: &str = "mongodb://localhost:27017/?directConnection=true";
+
+static MONGO_AVAILABLE: OnceCell<bool> = OnceCell::const_new();
+
+/// A client for the test replica set, or `None` when the default one is not
+/// running. An explicit `MONGODB_TEST_URI` asks for the Mongo half, so an
+/// unreachable one fails the tests rather than skipping them.
+async fn mongo_client() -> Option<Client> {
+    let requested = std::env::var("MONGODB_TEST_URI").ok();
+    let uri = requested
+        .clone()
+        .unwrap_or_else(|| DEFAULT_MONGODB_TEST_URI.to_string());
+    let mut options = ClientOptions::parse(&uri)
+        .await
+        .unwrap_or_else(|e| panic!("invalid MONGODB_TEST_URI {uri}: {e}"));
+    options.server_selection_timeout = Some(Duration::from_secs(1));
+    let client = Client::with_options(options).unwrap();
+
+    let available = *MONGO_AVAILABLE
+        .get_or_init(|| async {
+            let reachable = client
+                .database("admin")
+                .run_command(doc! { "ping": 1 })
+                .await
+                .is_ok();
+            if !reachable {
+                eprintln!("MongoDB is not reachable at {uri}, skipping the Mongo contract tests");
+            }
+            reachable
+        })
+        .await;
+    assert!(
+        available || requested.is_none(),
+        "MongoDB is not reachable at {uri}"
+    );
+    available.then_some(client)
+}
+
+/// A repository over a fresh database, or `None` when MongoDB is not running.
+async fn mongo_repository() -> Option<(MongoOfferRepository, Database)> {
+    let client = mongo_client().await?;
+    let database = client.database(&format!("erasmumu_test_{}", uuid::Uuid::new_v4().simple()));
+    let repository = MongoOfferRepository::new(
+        database.collection("offers"),
+        database.collection("outbox"),
+        database.collection("offer_history"),
+        database.collection("idempotency_keys"),
+    );
+    repository.ensure_indexes().await.unwrap();
+    Some((repository, database))
+}
+
+macro_rules! contract_tests {
+    ($($name:ident),* $(,)?) => {
+        mod memory {
+            use super::*;
+
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    super::$name(&InMemoryOfferRepository::new()).await;
+                }
+            )*
+        }
+
+        mod mongo {
+            use super::*;
+
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    let Some((repository, database)) = mongo_repository().await else {
+                        return;
+                    };
+                    super::$name(&repository).await;
+                    database.drop().await.unwrap();
+                }
+            )*
+        }
+    };
+}
+
+contract_tests!(
+    create_then_get_by_id,
+    create_many_inserts_every_offer,
+    search_applies_every_criterion,
+    search_pages_newest_first,
+    search_sorts_by_monthly_salary,
+    search_near_returns_nearest_first_with_distance,
+    search_text_ranks_by_relevance,
+    deleted_offers_are_hidden_but_readable,
+    stream_yields_matches_in_insertion_order,
+    list_all_only_returns_published_offers,
+    list_past_due_compares_the_reference_date,
+    reserve_takes_seats_until_filled,
+    concurrent_reservations_never_overbook,
+    scheduled_offers_stay_hidden_until_due,
+    update_requires_the_version_it_read,
+    concurrent_updates_let_one_writer_win,
+    history_lists_changes_oldest_first,
+    purge_deleted_removes_old_tombstones,
+    duplicates_are_rejected,
+    idempotent_creation_keeps_its_record,
+    facets_count_matching_offers,
+);
+
+/// The outbox is not part of the trait, so only the in-memory repository is
+/// checked here.
+#[tokio::test]
+async fn writes_announce_their_changes_in_the_outbox() {
+    let repository = InMemoryOfferRepository::new();
+    let created = store(
+        &repository,
+        Offer {
+            positions: 2,
+            remaining: 2,
+            ..offer("Announced")
+        },
+    )
+    .await;
+    modify(&repository, &created.id, |offer| offer.salary = 1300.0)
+        .await
+        .unwrap();
+    repository.reserve(&created.id, None).await.unwrap();
+    repository.reserve(&created.id, None).await.unwrap();
+
+    let existing = repository.get_by_id(&created.id).await.unwrap().unwrap();
+    let mut deleted = existing.clone();
+    deleted.mark_deleted(Utc::now());
+    let update = OfferUpdate::new(&existing, deleted);
+    repository
+        .update(&update.offer, &ChangeLog::deleted(&update, None))
+        .await
+        .unwrap();
+    let mut restored = update.offer.clone();
+    restored.restore();
+    let update = OfferUpdate::new(&update.offer, restored);
+    repository
+        .update(&update.offer, &ChangeLog::restored(&update, None))
+        .await
+        .unwrap();
+
+    let outbox = repository.outbox();
+    let keys: Vec<&str> = outbox
+        .iter()
+        .map(|message| message.routing_key.as_str())
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            "offer.created",
+            "offer.updated",
+            "offer.updated",
+            "offer.updated",
+            "offer.filled",
+            "offer.deleted",
+            "offer.restored",
+        ]
+    );
+    assert!(
+        outbox
+            .iter()
+            .all(|message| message.payload["id"] == created.id.as_str())
+    );
+    assert_eq!(outbox[1].payload["status"], "published");
+    assert_eq!(outbox[3].payload["status"], "filled");
+}
+
+#[tokio::test]
+async fn rejected_writes_announce_nothing() {
+    let repository = InMemoryOfferRepository::new();
+    let original = store(&repository, offer("Original")).await;
+    let copy = Offer {
+        link: original.link.clone(),
+        ..offer("Copy")
+    };
+    let created = repository
+        .create(&copy, &ChangeLog::created(&copy, None))
+        .await;
+    duplicate_of(created);
+
+    let mut renamed = original.clone();
+    renamed.title = "Renamed".to_string();
+    let update = OfferUpdate::new(&original, renamed);
+    let log = ChangeLog::updated(&update, None);
+    repository.update(&update.offer, &log).await.unwrap();
+    let stale = repository.update(&update.offer, &log).await;
+    assert!(matches!(stale, Err(OfferError::VersionMismatch { .. })));
+
+    let keys: Vec<String> = repository
+        .outbox()
+        .into_iter()
+        .map(|message| message.routing_key)
+        .collect();
+    assert_eq!(keys, ["offer.created", "offer.updated"]);
+}
+
+#[tokio::test]
+async fn outbox_messages_are_done_once_relayed_and_fanned_out() {
+    let Some((repository, database)) = mongo_repository().await else {
+        return;
+    };
+    let outbox = MongoOutboxRepository::new(database.collection("outbox"));
+    outbox.ensure_indexes().await.unwrap();
+    let created = store(&repository, offer("Relayed")).await;
+    modify(&repository, &created.id, |offer| offer.salary = 1300.0)
+        .await
+        .unwrap();
+
+    let pending = outbox.pending(10).await.unwrap();
+    let keys: Vec<&str> = pending.iter().map(|m| m.routing_key.as_str()).collect();
+    assert_eq!(keys, ["offer.created", "offer.updated"]);
+    let first = &pending[0].id;
+    outbox.mark_sent(first).await.unwrap();
+    assert_eq!(outbox.pending(10).await.unwrap().len(), 1);
+    assert_eq!(outbox.pending_fan_out(10).await.unwrap().len(), 2);
+
+    let done = |id: &str| {
+        let collection = database.collection::<mongodb::bson::Document>("outbox");
+        let filter = doc! { "id": id, "done_at": { "$exists": true } };
+        async move { collection.find_one(filter).await.unwrap().is_some() }
+    };
+    assert!(!done(first).await);
+    outbox.mark_fanned_out(first).await.unwrap();
+    assert!(done(first).await);
+    assert_eq!(outbox.pending_fan_out(10).await.unwrap().len(), 1);
+    database.drop().await.unwrap();
+}
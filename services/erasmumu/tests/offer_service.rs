@@ -0,0 +1,1510 @@
+use chrono::{DateTime, SubsecRound, TimeDelta, Utc};
+use erasmumu::adapters::persistence::memory::company_repository::InMemoryCompanyRepository;
+use erasmumu::adapters::persistence::memory::offer_repository::InMemoryOfferRepository;
+use erasmumu::application::company_service::{CompanyService, CreateCompanyParams};
+use erasmumu::application::expiry_sweeper::ExpirySweeper;
+use erasmumu::application::offer_service::{
+    CreateOfferParams, ImportRow, OfferService, UpdateOfferParams,
+};
+use erasmumu::application::publication_scheduler::PublicationScheduler;
+use erasmumu::application::tombstone_purger::TombstonePurger;
+use erasmumu::domain::company::Company;
+use erasmumu::domain::geo::{GeoNear, city_coordinates};
+use erasmumu::domain::history::HistoryAction;
+use erasmumu::domain::offer::{
+    ExpiryReference, Offer, OfferQuery, OfferSort, OfferStatus, PageRequest, Reservation,
+    SkillMatch, SortOrder,
+};
+use erasmumu::domain::ports::clock::Clock;
+use erasmumu::domain::ports::company_repository::{CompanyError, CompanyRepository};
+use erasmumu::domain::ports::offer_repository::OfferError;
+use erasmumu::domain::salary::{ExchangeRates, HOURS_PER_MONTH, SalaryPeriod};
+use std::sync::{Arc, Mutex};
+
+type Service = OfferService<InMemoryOfferRepository, InMemoryCompanyRepository>;
+
+fn service() -> (Service, Arc<InMemoryCompanyRepository>) {
+    let companies = Arc::new(InMemoryCompanyRepository::new());
+    let service = OfferService::new(
+        InMemoryOfferRepository::new(),
+        companies.clone(),
+        ExchangeRates::default(),
+    );
+    (service, companies)
+}
+
+/// A clock that only moves when told to.
+#[derive(Clone)]
+struct FakeClock(Arc<Mutex<DateTime<Utc>>>);
+
+impl FakeClock {
+    fn starting(now: DateTime<Utc>) -> Self {
+        Self(Arc::new(Mutex::new(now)))
+    }
+
+    fn at(rfc3339: &str) -> Self {
+        Self::starting(DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc())
+    }
+
+    fn set(&self, now: DateTime<Utc>) {
+        *self.0.lock().unwrap() = now;
+    }
+
+    fn advance(&self, by: TimeDelta) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+}
+
+fn params(title: &str) -> CreateOfferParams {
+    CreateOfferParams {
+        title: title.to_string(),
+        link: format!(
+            "https://example.com/offers/{}",
+            title.trim().to_lowercase().replace(' ', "-")
+        ),
+        city: "Lyon".to_string(),
+        domain: "AI".to_string(),
+        description: None,
+        salary: 1200.0,
+        currency: None,
+        salary_period: None,
+        start_date: "2026-06-01".to_string(),
+        end_date: "2026-09-30".to_string(),
+        status: None,
+        positions: None,
+        company_id: None,
+        publish_at: None,
+        skills: Vec::new(),
+        latitude: None,
+        longitude: None,
+    }
+}
+
+fn no_changes() -> UpdateOfferParams {
+    UpdateOfferParams {
+        title: None,
+        link: None,
+        city: None,
+        domain: None,
+        description: None,
+        salary: None,
+        currency: None,
+        salary_period: None,
+        start_date: None,
+        end_date: None,
+        positions: None,
+        available: None,
+        company_id: None,
+        publish_at: None,
+        skills: None,
+        latitude: None,
+        longitude: None,
+    }
+}
+
+fn page() -> PageRequest {
+    PageRequest {
+        offset: 0,
+        limit: 20,
+        sort: None,
+        order: SortOrder::Asc,
+    }
+}
+
+fn invalid_fields(result: Result<Offer, OfferError>) -> Vec<&'static str> {
+    match result {
+        Err(OfferError::ValidationFailed(fields)) => {
+            fields.into_iter().map(|field| field.field).collect()
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+}
+
+async fn search(service: &Service, query: OfferQuery) -> Vec<String> {
+    let mut titles: Vec<String> = service
+        .search_offers(query, page())
+        .await
+        .unwrap()
+        .offers
+        .into_iter()
+        .map(|offer| offer.title)
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[tokio::test]
+async fn create_offer_fills_in_defaults() {
+    let (service, _) = service();
+
+    let offer = service
+        .create_offer(
+            CreateOfferParams {
+                title: "  Data intern ".to_string(),
+                skills: vec![
+                    "Python".to_string(),
+                    " python".to_string(),
+                    "SQL".to_string(),
+                ],
+                ..params("")
+            },
+            Some("tests"),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(offer.title, "Data intern");
+    assert_eq!(offer.status, OfferStatus::Published);
+    assert!(offer.available);
+    assert_eq!((offer.positions, offer.remaining), (1, 1));
+    assert_eq!(offer.location, city_coordinates("Lyon"));
+    assert_eq!(offer.skills, ["python", "sql"]);
+    assert_eq!(offer.currency, "EUR");
+    assert_eq!(offer.salary_period, SalaryPeriod::Monthly);
+    assert_eq!(offer.salary_monthly_eur, 1200.0);
+    assert_eq!(offer.version, 1);
+
+    let history = service.offer_history(&offer.id).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].action, HistoryAction::Created);
+    assert_eq!(history[0].actor.as_deref(), Some("tests"));
+}
+
+#[tokio::test]
+async fn create_offer_reports_every_invalid_field() {
+    let (service, _) = service();
+
+    let result = service
+        .create_offer(
+            CreateOfferParams {
+                link: "ftp://example.com".to_string(),
+                salary: -1.0,
+                currency: Some("XYZ".to_string()),
+                end_date: "2026-05-01".to_string(),
+                positions: Some(0),
+                latitude: Some(120.0),
+                longitude: Some(2.0),
+                ..params(" ")
+            },
+            None,
+        )
+        .await;
+
+    assert_eq!(
+        invalid_fields(result),
+        [
+            "positions",
+            "latitude",
+            "currency",
+            "title",
+            "link",
+            "salary",
+            "end_date"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn offers_are_validated_on_update_too() {
+    let (service, _) = service();
+    let unparseable = service
+        .create_offer(
+            CreateOfferParams {
+                start_date: "2026-13-01".to_string(),
+                ..params("Bad date")
+            },
+            None,
+        )
+        .await;
+    assert_eq!(invalid_fields(unparseable), ["start_date"]);
+
+    let created = service.create_offer(params("Valid"), None).await.unwrap();
+    let invalid = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                title: Some("  ".to_string()),
+                end_date: Some("2026-05-01".to_string()),
+                ..no_changes()
+            },
+            None,
+            None,
+        )
+        .await
+        .map(|update| update.offer);
+    assert_eq!(invalid_fields(invalid), ["title", "end_date"]);
+    assert_eq!(service.get_offer(&created.id).await.unwrap().version, 1);
+}
+
+#[tokio::test]
+async fn create_offer_converts_salary_to_monthly_euros() {
+    let (service, _) = service();
+
+    let offer = service
+        .create_offer(
+            CreateOfferParams {
+                salary: 10.0,
+                currency: Some("chf".to_string()),
+                salary_period: Some(SalaryPeriod::Hourly),
+                ..params("Hourly in Geneva")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(offer.currency, "CHF");
+    let expected = 10.0 * 1.06 * HOURS_PER_MONTH;
+    assert!((offer.salary_monthly_eur - expected).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn salary_filters_compare_monthly_euros() {
+    let (service, _) = service();
+    let paid = |title: &str, salary, currency: &str, period| CreateOfferParams {
+        salary,
+        currency: Some(currency.to_string()),
+        salary_period: Some(period),
+        ..params(title)
+    };
+    // Roughly 1520, 1150 and 1380 euros a month.
+    for offer in [
+        paid("Hourly", 10.0, "EUR", SalaryPeriod::Hourly),
+        paid("Yearly", 15000.0, "USD", SalaryPeriod::Yearly),
+        paid("Monthly", 1300.0, "CHF", SalaryPeriod::Monthly),
+    ] {
+        service.create_offer(offer, None).await.unwrap();
+    }
+
+    let between = |min, max| OfferQuery {
+        min_salary: Some(min),
+        max_salary: Some(max),
+        ..Default::default()
+    };
+    assert_eq!(
+        search(&service, between(1200.0, 2000.0)).await,
+        ["Hourly", "Monthly"]
+    );
+    assert_eq!(search(&service, between(1000.0, 1200.0)).await, ["Yearly"]);
+
+    let yearly = service
+        .search_offers(between(1000.0, 1200.0), page())
+        .await
+        .unwrap()
+        .offers
+        .remove(0);
+    let update = service
+        .update_offer(
+            &yearly.id,
+            UpdateOfferParams {
+                currency: Some("eur".to_string()),
+                ..no_changes()
+            },
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(update.offer.currency, "EUR");
+    assert_eq!(update.offer.salary_monthly_eur, 1250.0);
+}
+
+#[tokio::test]
+async fn create_offer_checks_its_company() {
+    let (service, companies) = service();
+    companies
+        .create(&Company {
+            id: "acme".to_string(),
+            name: "Acme".to_string(),
+            website: None,
+            description: None,
+            cities: Vec::new(),
+        })
+        .await
+        .unwrap();
+
+    let unknown = service
+        .create_offer(
+            CreateOfferParams {
+                company_id: Some("nope".to_string()),
+                ..params("Unknown company")
+            },
+            None,
+        )
+        .await;
+    assert_eq!(invalid_fields(unknown), ["company_id"]);
+
+    let known = service
+        .create_offer(
+            CreateOfferParams {
+                company_id: Some(" acme ".to_string()),
+                ..params("Known company")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(known.company_id.as_deref(), Some("acme"));
+}
+
+#[tokio::test]
+async fn companies_are_only_deleted_once_their_offers_are() {
+    let (service, companies) = service();
+    let service = Arc::new(service);
+    let company_service = CompanyService::new(companies, service.clone());
+
+    let invalid = company_service
+        .create_company(CreateCompanyParams {
+            name: " ".to_string(),
+            website: Some("acme.example".to_string()),
+            description: None,
+            cities: vec!["Lyon".to_string(), "lyon ".to_string()],
+        })
+        .await;
+    let fields: Vec<&str> = match invalid {
+        Err(CompanyError::ValidationFailed(fields)) => fields.iter().map(|f| f.field).collect(),
+        other => panic!("expected a validation error, got {other:?}"),
+    };
+    assert_eq!(fields, ["name", "website"]);
+
+    let company = company_service
+        .create_company(CreateCompanyParams {
+            name: " Acme ".to_string(),
+            website: Some(" ".to_string()),
+            description: None,
+            cities: vec!["Lyon".to_string(), "lyon ".to_string()],
+        })
+        .await
+        .unwrap();
+    assert_eq!(company.name, "Acme");
+    assert_eq!(company.website, None);
+    assert_eq!(company.cities, ["Lyon"]);
+    let offer = service
+        .create_offer(
+            CreateOfferParams {
+                company_id: Some(company.id.clone()),
+                ..params("Acme intern")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let in_use = company_service.delete_company(&company.id).await;
+    assert!(matches!(in_use, Err(CompanyError::InUse(1))));
+    service.delete_offer(&offer.id, None).await.unwrap();
+    company_service.delete_company(&company.id).await.unwrap();
+    assert!(matches!(
+        company_service.get_company(&company.id).await,
+        Err(CompanyError::NotFound)
+    ));
+
+    // The company is gone by the time its offer comes back.
+    let restored = service.restore_offer(&offer.id, None).await.unwrap();
+    assert_eq!(restored.company_id, None);
+}
+
+#[tokio::test]
+async fn update_offer_changes_only_given_fields() {
+    let (service, _) = service();
+    let created = service
+        .create_offer(params("Original"), None)
+        .await
+        .unwrap();
+
+    let update = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                title: Some("Renamed".to_string()),
+                city: Some("Paris".to_string()),
+                salary: Some(1500.0),
+                ..no_changes()
+            },
+            None,
+            Some("tests"),
+        )
+        .await
+        .unwrap();
+
+    let offer = update.offer;
+    assert_eq!(offer.title, "Renamed");
+    assert_eq!(offer.location, city_coordinates("Paris"));
+    assert_eq!(offer.salary_monthly_eur, 1500.0);
+    assert_eq!(offer.domain, "AI");
+    assert_eq!(offer.start_date, created.start_date);
+    assert_eq!(offer.version, 2);
+
+    let mut changed: Vec<&str> = update.changes.iter().map(|c| c.field.as_str()).collect();
+    changed.sort();
+    assert_eq!(changed, ["city", "location", "salary", "title"]);
+
+    let history = service.offer_history(&created.id).await.unwrap();
+    assert_eq!(history.last().unwrap().action, HistoryAction::Updated);
+}
+
+#[tokio::test]
+async fn update_offer_honours_if_match() {
+    let (service, _) = service();
+    let created = service
+        .create_offer(params("Contended"), None)
+        .await
+        .unwrap();
+
+    service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                salary: Some(1300.0),
+                ..no_changes()
+            },
+            Some(&[1]),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let stale = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                salary: Some(1400.0),
+                ..no_changes()
+            },
+            Some(&[1]),
+            None,
+        )
+        .await;
+    assert!(matches!(
+        stale,
+        Err(OfferError::VersionMismatch { current: 2 })
+    ));
+    assert_eq!(service.get_offer(&created.id).await.unwrap().salary, 1300.0);
+
+    let fresh = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                salary: Some(1400.0),
+                ..no_changes()
+            },
+            Some(&[1, 2]),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(fresh.offer.version, 3);
+}
+
+#[tokio::test]
+async fn reserve_offer_tells_taken_offers_from_missing_ones() {
+    let (service, _) = service();
+    let created = service
+        .create_offer(params("One seat"), None)
+        .await
+        .unwrap();
+
+    let reserved = service.reserve_offer(&created.id, None).await.unwrap();
+    assert!(matches!(reserved, Reservation::Reserved(offer) if offer.remaining == 0));
+    let taken = service.reserve_offer(&created.id, None).await.unwrap();
+    assert!(matches!(taken, Reservation::AlreadyTaken));
+
+    let missing = service.reserve_offer("missing", None).await;
+    assert!(matches!(missing, Err(OfferError::NotFound)));
+    let deleted = service.create_offer(params("Deleted"), None).await.unwrap();
+    service.delete_offer(&deleted.id, None).await.unwrap();
+    let on_deleted = service.reserve_offer(&deleted.id, None).await;
+    assert!(matches!(on_deleted, Err(OfferError::NotFound)));
+}
+
+#[tokio::test]
+async fn scheduled_offers_cannot_be_reserved_yet() {
+    let (service, _) = service();
+    let publish_at = Utc::now() + TimeDelta::days(1);
+    let scheduled = service
+        .create_offer(
+            CreateOfferParams {
+                start_date: (publish_at + TimeDelta::days(30)).date_naive().to_string(),
+                end_date: (publish_at + TimeDelta::days(120)).date_naive().to_string(),
+                publish_at: Some(publish_at),
+                ..params("Not yet")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(scheduled.is_scheduled());
+
+    let reserved = service.reserve_offer(&scheduled.id, None).await;
+    assert!(matches!(reserved, Err(OfferError::NotFound)));
+    let stored = service.get_offer(&scheduled.id).await.unwrap();
+    assert_eq!(stored.remaining, 1);
+}
+
+#[tokio::test]
+async fn multi_seat_offers_stay_listed_until_their_last_seat_goes() {
+    let (service, _) = service();
+    let created = service
+        .create_offer(
+            CreateOfferParams {
+                positions: Some(3),
+                ..params("Three seats")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!((created.positions, created.remaining), (3, 3));
+
+    for remaining in [2, 1] {
+        service.reserve_offer(&created.id, None).await.unwrap();
+        let stored = service.get_offer(&created.id).await.unwrap();
+        assert_eq!(stored.remaining, remaining);
+        assert_eq!(
+            search(&service, OfferQuery::default()).await,
+            ["Three seats"]
+        );
+    }
+    service.reserve_offer(&created.id, None).await.unwrap();
+    assert!(search(&service, OfferQuery::default()).await.is_empty());
+    assert_eq!(
+        service.get_offer(&created.id).await.unwrap().status,
+        OfferStatus::Filled
+    );
+}
+
+#[tokio::test]
+async fn update_offer_keeps_taken_seats() {
+    let (service, _) = service();
+    let created = service
+        .create_offer(
+            CreateOfferParams {
+                positions: Some(2),
+                ..params("Two seats")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    service.reserve_offer(&created.id, None).await.unwrap();
+
+    let too_few = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                positions: Some(0),
+                ..no_changes()
+            },
+            None,
+            None,
+        )
+        .await
+        .map(|update| update.offer);
+    assert_eq!(invalid_fields(too_few), ["positions"]);
+
+    let more = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                positions: Some(3),
+                ..no_changes()
+            },
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!((more.offer.positions, more.offer.remaining), (3, 2));
+
+    let filled = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                positions: Some(1),
+                ..no_changes()
+            },
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(filled.offer.remaining, 0);
+    assert_eq!(filled.offer.status, OfferStatus::Filled);
+}
+
+#[tokio::test]
+async fn transitions_follow_the_offer_lifecycle() {
+    let (service, _) = service();
+    let offer = service
+        .create_offer(
+            CreateOfferParams {
+                status: Some(OfferStatus::Draft),
+                ..params("Lifecycle")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(!offer.available);
+    let transition = |next| service.transition_offer(&offer.id, next, None);
+
+    let filled_draft = transition(OfferStatus::Filled).await;
+    assert!(matches!(
+        filled_draft,
+        Err(OfferError::InvalidTransition {
+            from: OfferStatus::Draft,
+            to: OfferStatus::Filled
+        })
+    ));
+    for next in [
+        OfferStatus::Published,
+        OfferStatus::Expired,
+        OfferStatus::Published,
+        OfferStatus::Archived,
+    ] {
+        let update = transition(next).await.unwrap();
+        assert_eq!(update.offer.status, next);
+        assert_eq!(update.offer.available, next == OfferStatus::Published);
+    }
+    let unarchived = transition(OfferStatus::Published).await;
+    assert!(matches!(
+        unarchived,
+        Err(OfferError::InvalidTransition {
+            from: OfferStatus::Archived,
+            to: OfferStatus::Published
+        })
+    ));
+
+    let unchanged = transition(OfferStatus::Archived).await.unwrap();
+    assert!(unchanged.changes.is_empty());
+    assert_eq!(unchanged.offer.version, 5);
+}
+
+#[tokio::test]
+async fn filled_offers_are_republished_only_with_new_seats() {
+    let (service, _) = service();
+    let created = service
+        .create_offer(params("Last seat"), None)
+        .await
+        .unwrap();
+    service.reserve_offer(&created.id, None).await.unwrap();
+
+    let republished = service
+        .transition_offer(&created.id, OfferStatus::Published, None)
+        .await;
+    assert!(matches!(republished, Err(OfferError::NoSeatsLeft)));
+    let stored = service.get_offer(&created.id).await.unwrap();
+    assert_eq!(stored.status, OfferStatus::Filled);
+    assert_eq!(stored.remaining, 0);
+
+    let reopened = service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                positions: Some(2),
+                ..no_changes()
+            },
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(reopened.offer.status, OfferStatus::Published);
+    assert_eq!((reopened.offer.positions, reopened.offer.remaining), (2, 1));
+}
+
+#[tokio::test]
+async fn search_only_returns_available_offers_by_default() {
+    let (service, _) = service();
+    service.create_offer(params("Open"), None).await.unwrap();
+    service
+        .create_offer(
+            CreateOfferParams {
+                status: Some(OfferStatus::Draft),
+                ..params("Draft")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let filled = service.create_offer(params("Filled"), None).await.unwrap();
+    service.reserve_offer(&filled.id, None).await.unwrap();
+    let deleted = service.create_offer(params("Deleted"), None).await.unwrap();
+    service.delete_offer(&deleted.id, None).await.unwrap();
+
+    let by_city = OfferQuery {
+        city: Some("Lyon".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(search(&service, by_city.clone()).await, ["Open"]);
+    assert_eq!(
+        search(
+            &service,
+            OfferQuery {
+                available: Some(false),
+                ..by_city.clone()
+            }
+        )
+        .await,
+        ["Draft", "Filled"]
+    );
+    assert_eq!(
+        search(
+            &service,
+            OfferQuery {
+                status: Some(OfferStatus::Filled),
+                ..by_city.clone()
+            }
+        )
+        .await,
+        ["Filled"]
+    );
+
+    let browsed = service.browse_offers(page()).await.unwrap();
+    assert_eq!(browsed.total, 1);
+}
+
+#[tokio::test]
+async fn browse_offers_pages_every_published_offer() {
+    let (service, _) = service();
+    for title in ["First", "Second", "Third"] {
+        service.create_offer(params(title), None).await.unwrap();
+    }
+    service
+        .create_offer(
+            CreateOfferParams {
+                status: Some(OfferStatus::Draft),
+                ..params("Draft")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let deleted = service.create_offer(params("Deleted"), None).await.unwrap();
+    service.delete_offer(&deleted.id, None).await.unwrap();
+
+    let browse = |offset| PageRequest {
+        offset,
+        limit: 2,
+        ..page()
+    };
+    let first = service.browse_offers(browse(0)).await.unwrap();
+    assert_eq!(first.total, 3);
+    let titles: Vec<&str> = first.offers.iter().map(|o| o.title.as_str()).collect();
+    assert_eq!(titles, ["Third", "Second"]);
+    let last = service.browse_offers(browse(2)).await.unwrap();
+    assert_eq!(last.offers.len(), 1);
+    assert_eq!(last.offers[0].title, "First");
+
+    let empty = service
+        .browse_offers(PageRequest { limit: 0, ..page() })
+        .await;
+    assert!(matches!(empty, Err(OfferError::InvalidData(_))));
+}
+
+#[tokio::test]
+async fn scheduled_offers_go_live_when_due() {
+    let (service, _) = service();
+    let service = Arc::new(service);
+    let clock = FakeClock::starting(Utc::now());
+    let scheduler = PublicationScheduler::new(service.clone(), clock.clone());
+    let publish_at = clock.now() + TimeDelta::days(1);
+    let ahead = |title: &str, days: i64| CreateOfferParams {
+        start_date: (publish_at + TimeDelta::days(days))
+            .date_naive()
+            .to_string(),
+        end_date: (publish_at + TimeDelta::days(days + 90))
+            .date_naive()
+            .to_string(),
+        publish_at: Some(publish_at),
+        ..params(title)
+    };
+    let scheduled = service
+        .create_offer(ahead("Scheduled", 30), None)
+        .await
+        .unwrap();
+    assert!(scheduled.is_scheduled());
+    let past = service
+        .create_offer(
+            CreateOfferParams {
+                publish_at: Some(Utc::now() - TimeDelta::minutes(1)),
+                ..params("Already live")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(!past.is_scheduled());
+    assert_eq!(
+        search(&service, OfferQuery::default()).await,
+        ["Already live"]
+    );
+
+    let after_end = service
+        .create_offer(ahead("After its end", -100), None)
+        .await;
+    assert_eq!(invalid_fields(after_end), ["publish_at"]);
+    let on_live = service
+        .update_offer(
+            &past.id,
+            UpdateOfferParams {
+                publish_at: Some(publish_at),
+                ..no_changes()
+            },
+            None,
+            None,
+        )
+        .await;
+    assert!(matches!(on_live, Err(OfferError::ValidationFailed(_))));
+
+    assert!(scheduler.publish().await.unwrap().is_empty());
+    // Stored publication times are truncated to whole seconds.
+    clock.set(publish_at.trunc_subsecs(0) - TimeDelta::seconds(1));
+    assert!(scheduler.publish().await.unwrap().is_empty());
+    clock.advance(TimeDelta::seconds(1));
+    let published = scheduler.publish().await.unwrap();
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0].id, scheduled.id);
+    assert_eq!(
+        search(&service, OfferQuery::default()).await,
+        ["Already live", "Scheduled"]
+    );
+
+    let history = service.offer_history(&scheduled.id).await.unwrap();
+    let went_live = history.last().unwrap();
+    assert_eq!(went_live.actor.as_deref(), Some("publication-scheduler"));
+    assert_eq!(went_live.changes[0].field, "publish_at");
+    assert!(scheduler.publish().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn legacy_available_flag_only_publishes_drafts() {
+    let (service, _) = service();
+    let draft = service
+        .create_offer(
+            CreateOfferParams {
+                status: Some(OfferStatus::Draft),
+                ..params("Draft")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let available = |available| UpdateOfferParams {
+        available: Some(available),
+        ..no_changes()
+    };
+
+    let published = service
+        .update_offer(&draft.id, available(true), None, None)
+        .await
+        .unwrap();
+    assert_eq!(published.offer.status, OfferStatus::Published);
+    assert!(published.offer.available);
+
+    let closed = service
+        .update_offer(&draft.id, available(false), None, None)
+        .await
+        .unwrap();
+    assert_eq!(closed.offer.status, OfferStatus::Filled);
+    assert!(!closed.offer.available);
+
+    let reopened = service
+        .update_offer(&draft.id, available(true), None, None)
+        .await;
+    assert!(matches!(
+        reopened,
+        Err(OfferError::InvalidTransition {
+            from: OfferStatus::Filled,
+            to: OfferStatus::Published
+        })
+    ));
+    assert_eq!(
+        service.get_offer(&draft.id).await.unwrap().status,
+        OfferStatus::Filled
+    );
+}
+
+#[tokio::test]
+async fn search_combines_every_criterion() {
+    let (service, _) = service();
+    let create = |params: CreateOfferParams| {
+        let service = &service;
+        async move { service.create_offer(params, None).await.unwrap() }
+    };
+    create(CreateOfferParams {
+        salary: 1500.0,
+        start_date: "2026-07-01".to_string(),
+        ..params("Match")
+    })
+    .await;
+    create(CreateOfferParams {
+        city: "Paris".to_string(),
+        salary: 1500.0,
+        start_date: "2026-07-01".to_string(),
+        ..params("Other city")
+    })
+    .await;
+    create(CreateOfferParams {
+        domain: "Finance".to_string(),
+        salary: 1500.0,
+        start_date: "2026-07-01".to_string(),
+        ..params("Other domain")
+    })
+    .await;
+    create(CreateOfferParams {
+        salary: 900.0,
+        start_date: "2026-07-01".to_string(),
+        ..params("Too cheap")
+    })
+    .await;
+    create(CreateOfferParams {
+        salary: 1500.0,
+        ..params("Starts too early")
+    })
+    .await;
+    create(CreateOfferParams {
+        salary: 1500.0,
+        start_date: "2026-07-01".to_string(),
+        end_date: "2027-03-31".to_string(),
+        ..params("Ends too late")
+    })
+    .await;
+
+    let query = OfferQuery {
+        city: Some("Lyon".to_string()),
+        domain: Some("AI".to_string()),
+        min_salary: Some(1000.0),
+        max_salary: Some(2000.0),
+        start_from: Some("2026-06-15".parse().unwrap()),
+        end_until: Some("2026-12-31".parse().unwrap()),
+        ..Default::default()
+    };
+    assert_eq!(search(&service, query).await, ["Match"]);
+    assert_eq!(search(&service, OfferQuery::default()).await.len(), 6);
+}
+
+#[tokio::test]
+async fn search_pages_sorted_results_within_bounds() {
+    let (service, _) = service();
+    for (title, salary) in [("Low", 1000.0), ("Mid", 1500.0), ("High", 2000.0)] {
+        service
+            .create_offer(
+                CreateOfferParams {
+                    salary,
+                    ..params(title)
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    let by_salary = |offset, limit| PageRequest {
+        offset,
+        limit,
+        sort: Some(OfferSort::Salary),
+        order: SortOrder::Desc,
+    };
+    let second = service
+        .search_offers(OfferQuery::default(), by_salary(1, 1))
+        .await
+        .unwrap();
+    assert_eq!(second.total, 3);
+    assert_eq!((second.offset, second.limit), (1, 1));
+    assert_eq!(second.offers[0].title, "Mid");
+    let past_the_end = service
+        .search_offers(OfferQuery::default(), by_salary(3, 100))
+        .await
+        .unwrap();
+    assert_eq!(past_the_end.total, 3);
+    assert!(past_the_end.offers.is_empty());
+
+    for limit in [0, 101] {
+        let result = service
+            .search_offers(OfferQuery::default(), by_salary(0, limit))
+            .await;
+        assert!(matches!(result, Err(OfferError::InvalidData(_))));
+    }
+}
+
+#[tokio::test]
+async fn search_rejects_contradictory_salary_bounds() {
+    let (service, _) = service();
+
+    let result = service
+        .search_offers(
+            OfferQuery {
+                min_salary: Some(2000.0),
+                max_salary: Some(1000.0),
+                ..Default::default()
+            },
+            page(),
+        )
+        .await;
+    assert!(matches!(result, Err(OfferError::InvalidData(_))));
+}
+
+#[tokio::test]
+async fn search_near_uses_given_or_known_coordinates() {
+    let (service, _) = service();
+    for city in ["Lyon", "Grenoble", "Paris"] {
+        service
+            .create_offer(
+                CreateOfferParams {
+                    city: city.to_string(),
+                    ..params(&format!("In {city}"))
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    let unknown = service
+        .create_offer(
+            CreateOfferParams {
+                city: "Nowhere".to_string(),
+                ..params("Unknown city")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(unknown.location, None);
+    // Explicit coordinates win over the city's: this one is in Lyon.
+    service
+        .create_offer(
+            CreateOfferParams {
+                city: "Paris".to_string(),
+                latitude: Some(45.76),
+                longitude: Some(4.84),
+                ..params("Remote from Paris")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let near_lyon = |radius_km| OfferQuery {
+        near: Some(GeoNear {
+            center: city_coordinates("Lyon").unwrap(),
+            radius_km,
+        }),
+        ..Default::default()
+    };
+    let found = service
+        .search_offers(near_lyon(150.0), page())
+        .await
+        .unwrap();
+    let titles: Vec<&str> = found.offers.iter().map(|o| o.title.as_str()).collect();
+    assert_eq!(titles, ["In Lyon", "Remote from Paris", "In Grenoble"]);
+    assert!(found.offers[0].distance_km.unwrap() < 1.0);
+    assert!((80.0..110.0).contains(&found.offers[2].distance_km.unwrap()));
+
+    for radius_km in [0.0, 1000.5] {
+        let result = service.search_offers(near_lyon(radius_km), page()).await;
+        assert!(matches!(result, Err(OfferError::InvalidData(_))));
+    }
+}
+
+#[tokio::test]
+async fn search_matches_any_or_all_skills() {
+    let (service, _) = service();
+    for (title, skills) in [
+        ("Rust and SQL", vec!["Rust", "SQL"]),
+        ("Rust only", vec!["rust"]),
+        ("Python", vec!["Python"]),
+    ] {
+        service
+            .create_offer(
+                CreateOfferParams {
+                    skills: skills.into_iter().map(String::from).collect(),
+                    ..params(title)
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    let too_many = service
+        .create_offer(
+            CreateOfferParams {
+                skills: (0..21).map(|index| format!("skill {index}")).collect(),
+                ..params("Too many skills")
+            },
+            None,
+        )
+        .await;
+    assert_eq!(invalid_fields(too_many), ["skills"]);
+
+    let skills = |skill_match| OfferQuery {
+        skills: vec![" RUST ".to_string(), "sql".to_string(), " ".to_string()],
+        skill_match,
+        ..Default::default()
+    };
+    assert_eq!(
+        search(&service, skills(SkillMatch::Any)).await,
+        ["Rust and SQL", "Rust only"]
+    );
+    assert_eq!(
+        search(&service, skills(SkillMatch::All)).await,
+        ["Rust and SQL"]
+    );
+}
+
+#[tokio::test]
+async fn search_by_text_cannot_be_combined_with_near() {
+    let (service, _) = service();
+    service
+        .create_offer(
+            CreateOfferParams {
+                description: Some("Deep learning for medical imaging.".to_string()),
+                ..params("Research intern")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    service
+        .create_offer(params("Sales intern"), None)
+        .await
+        .unwrap();
+
+    let text = |q: &str| OfferQuery {
+        text: Some(q.to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        search(&service, text("  Imaging ")).await,
+        ["Research intern"]
+    );
+    assert_eq!(search(&service, text("   ")).await.len(), 2);
+
+    let result = service
+        .search_offers(
+            OfferQuery {
+                near: Some(GeoNear {
+                    center: city_coordinates("Lyon").unwrap(),
+                    radius_km: 50.0,
+                }),
+                ..text("imaging")
+            },
+            page(),
+        )
+        .await;
+    assert!(matches!(result, Err(OfferError::InvalidData(_))));
+}
+
+#[tokio::test]
+async fn create_offer_rejects_duplicates() {
+    let (service, companies) = service();
+    companies
+        .create(&Company {
+            id: "acme".to_string(),
+            name: "Acme".to_string(),
+            website: None,
+            description: None,
+            cities: Vec::new(),
+        })
+        .await
+        .unwrap();
+    let original = service
+        .create_offer(
+            CreateOfferParams {
+                company_id: Some("acme".to_string()),
+                ..params("Original")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let result = service
+        .create_offer(
+            CreateOfferParams {
+                city: " lyon ".to_string(),
+                link: "https://example.com/offers/elsewhere".to_string(),
+                company_id: Some("acme".to_string()),
+                ..params("ORIGINAL")
+            },
+            None,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(OfferError::Duplicate { existing_id }) if existing_id == original.id
+    ));
+}
+
+#[tokio::test]
+async fn import_keeps_valid_rows_and_reports_the_others() {
+    let (service, _) = service();
+    let rows = vec![
+        ImportRow {
+            row: 1,
+            params: Ok(params("Valid")),
+        },
+        ImportRow {
+            row: 2,
+            params: Err("missing field `title`".to_string()),
+        },
+        ImportRow {
+            row: 3,
+            params: Ok(CreateOfferParams {
+                salary: -1.0,
+                ..params("Negative salary")
+            }),
+        },
+        ImportRow {
+            row: 4,
+            params: Ok(CreateOfferParams {
+                company_id: Some("nope".to_string()),
+                ..params("Unknown company")
+            }),
+        },
+        ImportRow {
+            row: 5,
+            params: Ok(params("Also valid")),
+        },
+    ];
+    let report = service.import_offers(rows, Some("importer")).await;
+
+    assert_eq!(report.received, 5);
+    let accepted: Vec<usize> = report.accepted.iter().map(|row| row.row).collect();
+    assert_eq!(accepted, [1, 5]);
+    let errors: Vec<(usize, Vec<&str>)> = report
+        .errors
+        .iter()
+        .map(|error| (error.row, error.fields.iter().map(|f| f.field).collect()))
+        .collect();
+    assert_eq!(
+        errors,
+        [(2, vec![]), (3, vec!["salary"]), (4, vec!["company_id"])]
+    );
+    assert!(report.errors[0].error.contains("title"));
+
+    let history = service.offer_history(&report.accepted[0].id).await.unwrap();
+    assert_eq!(history[0].actor.as_deref(), Some("importer"));
+    assert_eq!(
+        search(&service, OfferQuery::default()).await,
+        ["Also valid", "Valid"]
+    );
+}
+
+#[tokio::test]
+async fn import_reports_duplicate_rows() {
+    let (service, _) = service();
+    let stored = service.create_offer(params("Stored"), None).await.unwrap();
+
+    let rows = ["Stored", "New", "New", "Other"]
+        .into_iter()
+        .enumerate()
+        .map(|(index, title)| ImportRow {
+            row: index + 1,
+            params: Ok(params(title)),
+        })
+        .collect();
+    let report = service.import_offers(rows, None).await;
+
+    let accepted: Vec<usize> = report.accepted.iter().map(|row| row.row).collect();
+    assert_eq!(accepted, [2, 4]);
+    let duplicates: Vec<(usize, Option<&str>)> = report
+        .errors
+        .iter()
+        .map(|error| (error.row, error.duplicate_of.as_deref()))
+        .collect();
+    assert_eq!(
+        duplicates,
+        [
+            (1, Some(stored.id.as_str())),
+            (3, Some(report.accepted[0].id.as_str()))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn idempotent_creation_replays_retries() {
+    let (service, _) = service();
+
+    let first = service
+        .create_offer_idempotent(params("Retried"), "key", "request", None)
+        .await
+        .unwrap();
+    assert!(!first.replayed);
+
+    let retry = service
+        .create_offer_idempotent(params("Retried"), "key", "request", None)
+        .await
+        .unwrap();
+    assert!(retry.replayed);
+    assert_eq!(retry.offer.id, first.offer.id);
+
+    let reused = service
+        .create_offer_idempotent(params("Another"), "key", "another request", None)
+        .await;
+    assert!(matches!(reused, Err(OfferError::IdempotencyKeyReused)));
+
+    let all = service.browse_offers(page()).await.unwrap();
+    assert_eq!(all.total, 1);
+}
+
+#[tokio::test]
+async fn expiry_sweep_expires_offers_ended_before_today() {
+    let (service, _) = service();
+    let service = Arc::new(service);
+    let clock = FakeClock::at("2026-07-15T23:30:00Z");
+    let sweeper = ExpirySweeper::new(service.clone(), clock.clone(), ExpiryReference::EndDate);
+    let ending = |title: &str, end_date: &str, status| CreateOfferParams {
+        end_date: end_date.to_string(),
+        status,
+        ..params(title)
+    };
+
+    let yesterday = service
+        .create_offer(ending("Ended yesterday", "2026-07-14", None), None)
+        .await
+        .unwrap();
+    let today = service
+        .create_offer(ending("Ends today", "2026-07-15", None), None)
+        .await
+        .unwrap();
+    let draft = service
+        .create_offer(
+            ending("Draft", "2026-07-01", Some(OfferStatus::Draft)),
+            None,
+        )
+        .await
+        .unwrap();
+    let expired = service
+        .create_offer(ending("Expired", "2026-07-01", None), None)
+        .await
+        .unwrap();
+    service
+        .transition_offer(&expired.id, OfferStatus::Expired, None)
+        .await
+        .unwrap();
+
+    let swept = sweeper.sweep().await.unwrap();
+    assert_eq!(swept.len(), 1);
+    assert_eq!(swept[0].id, yesterday.id);
+    assert_eq!(swept[0].status, OfferStatus::Expired);
+    assert!(!swept[0].available);
+
+    let status = |id: String| {
+        let service = service.clone();
+        async move { service.get_offer(&id).await.unwrap().status }
+    };
+    assert_eq!(status(today.id.clone()).await, OfferStatus::Published);
+    assert_eq!(status(draft.id.clone()).await, OfferStatus::Draft);
+    let history = service.offer_history(&expired.id).await.unwrap();
+    assert_eq!(history.len(), 2);
+
+    clock.advance(TimeDelta::hours(1));
+    let swept = sweeper.sweep().await.unwrap();
+    assert_eq!(swept.len(), 1);
+    assert_eq!(swept[0].id, today.id);
+    assert!(sweeper.sweep().await.unwrap().is_empty());
+    assert_eq!(status(draft.id).await, OfferStatus::Draft);
+}
+
+#[tokio::test]
+async fn history_records_who_changed_what() {
+    let (service, _) = service();
+    let created = service
+        .create_offer(params("Audited"), Some("recruiter"))
+        .await
+        .unwrap();
+    service
+        .update_offer(
+            &created.id,
+            UpdateOfferParams {
+                salary: Some(1300.0),
+                ..no_changes()
+            },
+            None,
+            Some("editor"),
+        )
+        .await
+        .unwrap();
+    service
+        .reserve_offer(&created.id, Some("student"))
+        .await
+        .unwrap();
+    service
+        .delete_offer(&created.id, Some("admin"))
+        .await
+        .unwrap();
+    service
+        .restore_offer(&created.id, Some("admin"))
+        .await
+        .unwrap();
+
+    let history = service.offer_history(&created.id).await.unwrap();
+    let entries: Vec<(HistoryAction, Option<&str>)> = history
+        .iter()
+        .map(|entry| (entry.action, entry.actor.as_deref()))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (HistoryAction::Created, Some("recruiter")),
+            (HistoryAction::Updated, Some("editor")),
+            (HistoryAction::Updated, Some("student")),
+            (HistoryAction::Deleted, Some("admin")),
+            (HistoryAction::Restored, Some("admin")),
+        ]
+    );
+    let salary = &history[1].changes[0];
+    assert_eq!(
+        (salary.field.as_str(), &salary.from, &salary.to),
+        ("salary", &1200.0.into(), &1300.0.into())
+    );
+
+    let missing = service.offer_history("missing").await;
+    assert!(matches!(missing, Err(OfferError::NotFound)));
+}
+
+#[tokio::test]
+async fn deleted_offers_can_be_restored_until_purged() {
+    let (service, _) = service();
+    let service = Arc::new(service);
+    let clock = FakeClock::starting(Utc::now());
+    let purger = TombstonePurger::new(service.clone(), clock.clone(), TimeDelta::days(30));
+    let kept = service.create_offer(params("Kept"), None).await.unwrap();
+    let deleted = service.create_offer(params("Deleted"), None).await.unwrap();
+
+    let tombstone = service.delete_offer(&deleted.id, None).await.unwrap();
+    assert!(tombstone.is_deleted());
+    assert!(service.get_offer(&deleted.id).await.unwrap().is_deleted());
+    assert_eq!(search(&service, OfferQuery::default()).await, ["Kept"]);
+    let edited = service
+        .update_offer(&deleted.id, no_changes(), None, None)
+        .await;
+    assert!(matches!(edited, Err(OfferError::NotFound)));
+    let deleted_again = service.delete_offer(&deleted.id, None).await;
+    assert!(matches!(deleted_again, Err(OfferError::NotFound)));
+    let live = service.restore_offer(&kept.id, None).await;
+    assert!(matches!(live, Err(OfferError::InvalidData(_))));
+
+    let restored = service.restore_offer(&deleted.id, None).await.unwrap();
+    assert!(!restored.is_deleted());
+    assert_eq!(
+        search(&service, OfferQuery::default()).await,
+        ["Deleted", "Kept"]
+    );
+
+    service.delete_offer(&deleted.id, None).await.unwrap();
+    clock.advance(TimeDelta::days(29));
+    assert_eq!(purger.purge().await.unwrap(), 0);
+    clock.advance(TimeDelta::days(2));
+    assert_eq!(purger.purge().await.unwrap(), 1);
+    let purged = service.get_offer(&deleted.id).await;
+    assert!(matches!(purged, Err(OfferError::NotFound)));
+    assert!(!service.get_offer(&kept.id).await.unwrap().is_deleted());
+}
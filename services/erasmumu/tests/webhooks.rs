@@ -0,0 +1,339 @@
+use axum::{Router, body::Bytes, extract::State, http::HeaderMap, http::StatusCode, routing::post};
+use chrono::{DateTime, TimeDelta, Utc};
+use erasmumu::adapters::persistence::memory::webhook_repository::InMemoryWebhookRepository;
+use erasmumu::adapters::webhook::sender::HttpWebhookSender;
+use erasmumu::application::webhook_service::{
+    CreateSubscriptionParams, UpdateSubscriptionParams, WebhookService,
+};
+use erasmumu::domain::outbox::OutboxMessage;
+use erasmumu::domain::ports::webhook_repository::WebhookError;
+use erasmumu::domain::webhook::{
+    DELIVERY_HEADER, DeliveryStatus, EVENT_HEADER, FIRST_RETRY_DELAY, MAX_DELIVERY_ATTEMPTS,
+    SIGNATURE_HEADER, WebhookSubscription, retry_delay,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Service = WebhookService<InMemoryWebhookRepository, HttpWebhookSender>;
+
+const SECRET: &str = "a-partner-school-secret";
+
+struct Received {
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+#[derive(Clone)]
+struct Receiver {
+    requests: Arc<Mutex<Vec<Received>>>,
+    /// Status codes answered in turn, 200 once they run out.
+    responses: Arc<Mutex<Vec<StatusCode>>>,
+}
+
+impl Receiver {
+    fn requests(&self) -> std::sync::MutexGuard<'_, Vec<Received>> {
+        self.requests.lock().unwrap()
+    }
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    receiver.requests().push(Received { headers, body });
+    let mut responses = receiver.responses.lock().unwrap();
+    if responses.is_empty() {
+        StatusCode::OK
+    } else {
+        responses.remove(0)
+    }
+}
+
+/// Starts a local receiver answering with `responses` in turn and returns its URL.
+async fn receiver(responses: Vec<StatusCode>) -> (String, Receiver) {
+    let receiver = Receiver {
+        requests: Arc::new(Mutex::new(Vec::new())),
+        responses: Arc::new(Mutex::new(responses)),
+    };
+    let app = Router::new()
+        .route("/hooks", post(receive))
+        .route(
+            "/black-hole",
+            post(|| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                StatusCode::OK
+            }),
+        )
+        .with_state(receiver.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}/hooks", addr), receiver)
+}
+
+fn service() -> Service {
+    service_with_timeout(Duration::from_secs(5))
+}
+
+fn service_with_timeout(timeout: Duration) -> Service {
+    WebhookService::new(
+        InMemoryWebhookRepository::new(),
+        HttpWebhookSender::new(timeout).unwrap(),
+    )
+}
+
+async fn subscribe(service: &Service, url: &str, events: &[&str]) -> WebhookSubscription {
+    service
+        .create_subscription(CreateSubscriptionParams {
+            url: url.to_string(),
+            events: events.iter().map(|event| event.to_string()).collect(),
+            secret: Some(SECRET.to_string()),
+        })
+        .await
+        .unwrap()
+}
+
+fn message(routing_key: &str) -> OutboxMessage {
+    OutboxMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        routing_key: routing_key.to_string(),
+        payload: serde_json::json!({ "offer_id": "offer-1", "title": "Data engineer" }),
+        created_at: Utc::now(),
+        attempts: 0,
+    }
+}
+
+fn start() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2026-10-01T09:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+fn field_errors(err: WebhookError) -> Vec<&'static str> {
+    match err {
+        WebhookError::ValidationFailed(fields) => fields.iter().map(|f| f.field).collect(),
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn delivers_signed_events_the_subscription_wants() {
+    let (url, receiver) = receiver(Vec::new()).await;
+    let service = service();
+    let subscription = subscribe(&service, &url, &["offer.created"]).await;
+
+    assert_eq!(
+        service
+            .fan_out(&message("offer.updated"), start())
+            .await
+            .unwrap(),
+        0
+    );
+    let created = message("offer.created");
+    assert_eq!(service.fan_out(&created, start()).await.unwrap(), 1);
+
+    let delivered = service.deliver_due(start()).await.unwrap();
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].status, DeliveryStatus::Delivered);
+    assert_eq!(delivered[0].subscription_id, subscription.id);
+
+    let requests = receiver.requests();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.headers[EVENT_HEADER], "offer.created");
+    assert_eq!(request.headers[DELIVERY_HEADER], delivered[0].id.as_str());
+    assert_eq!(request.headers["content-type"], "application/json");
+
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["id"], created.id.as_str());
+    assert_eq!(body["event"], "offer.created");
+    assert_eq!(body["data"]["offer_id"], "offer-1");
+
+    // Receivers check the signature with nothing but the secret.
+    let header = request.headers[SIGNATURE_HEADER].to_str().unwrap();
+    let (timestamp, signature) = header
+        .strip_prefix("t=")
+        .and_then(|rest| rest.split_once(",v1="))
+        .unwrap();
+    assert_eq!(timestamp, start().timestamp().to_string());
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(&request.body);
+    mac.verify_slice(&hex::decode(signature).unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn retries_failed_deliveries_with_backoff_and_logs_every_attempt() {
+    let (url, receiver) = receiver(vec![StatusCode::INTERNAL_SERVER_ERROR]).await;
+    let service = service();
+    let subscription = subscribe(&service, &url, &[]).await;
+    service
+        .fan_out(&message("offer.filled"), start())
+        .await
+        .unwrap();
+
+    let first = service.deliver_due(start()).await.unwrap();
+    assert_eq!(first[0].status, DeliveryStatus::Pending);
+    assert_eq!(first[0].next_attempt_at, Some(start() + FIRST_RETRY_DELAY));
+
+    let early = start() + FIRST_RETRY_DELAY - TimeDelta::seconds(1);
+    assert!(service.deliver_due(early).await.unwrap().is_empty());
+    assert_eq!(receiver.requests().len(), 1);
+
+    let retried = service
+        .deliver_due(start() + FIRST_RETRY_DELAY)
+        .await
+        .unwrap();
+    assert_eq!(retried[0].status, DeliveryStatus::Delivered);
+    assert_eq!(retried[0].next_attempt_at, None);
+    assert_eq!(receiver.requests().len(), 2);
+
+    let log = service.deliveries(&subscription.id, 20).await.unwrap();
+    assert_eq!(log.len(), 1);
+    let attempts: Vec<Option<u16>> = log[0].attempts.iter().map(|a| a.status_code).collect();
+    assert_eq!(attempts, vec![Some(500), Some(200)]);
+}
+
+#[tokio::test]
+async fn gives_up_after_the_last_attempt() {
+    let (url, receiver) = receiver(vec![
+        StatusCode::BAD_GATEWAY;
+        MAX_DELIVERY_ATTEMPTS as usize
+    ])
+    .await;
+    let service = service();
+    let subscription = subscribe(&service, &url, &[]).await;
+    service
+        .fan_out(&message("offer.deleted"), start())
+        .await
+        .unwrap();
+
+    let mut now = start();
+    for attempt in 1..=MAX_DELIVERY_ATTEMPTS {
+        let attempted = service.deliver_due(now).await.unwrap();
+        assert_eq!(attempted.len(), 1);
+        now += retry_delay(attempt);
+    }
+    assert!(service.deliver_due(now).await.unwrap().is_empty());
+    assert_eq!(receiver.requests().len(), MAX_DELIVERY_ATTEMPTS as usize);
+
+    let log = service.deliveries(&subscription.id, 20).await.unwrap();
+    assert_eq!(log[0].status, DeliveryStatus::Failed);
+    assert_eq!(log[0].attempts.len(), MAX_DELIVERY_ATTEMPTS as usize);
+    assert_eq!(log[0].next_attempt_at, None);
+}
+
+#[tokio::test]
+async fn records_connection_errors_as_failed_attempts() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+    drop(listener);
+    let service = service();
+    subscribe(&service, &url, &[]).await;
+    service
+        .fan_out(&message("offer.created"), start())
+        .await
+        .unwrap();
+
+    let attempted = service.deliver_due(start()).await.unwrap();
+    assert_eq!(attempted[0].status, DeliveryStatus::Pending);
+    assert_eq!(attempted[0].attempts[0].status_code, None);
+    assert!(attempted[0].attempts[0].error.is_some());
+}
+
+#[tokio::test]
+async fn validates_subscriptions() {
+    let service = service();
+
+    let err = service
+        .create_subscription(CreateSubscriptionParams {
+            url: "ftp://school.example".to_string(),
+            events: vec!["offer.created".to_string(), "offer.sold".to_string()],
+            secret: Some("short".to_string()),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(field_errors(err), vec!["url", "events", "secret"]);
+
+    let generated = service
+        .create_subscription(CreateSubscriptionParams {
+            url: " https://school.example/hooks ".to_string(),
+            events: vec!["offer.created".to_string(), "offer.created".to_string()],
+            secret: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(generated.url, "https://school.example/hooks");
+    assert_eq!(generated.events, vec!["offer.created"]);
+    assert!(generated.secret.starts_with("whsec_"));
+
+    let err = service
+        .update_subscription(
+            &generated.id,
+            UpdateSubscriptionParams {
+                url: None,
+                events: Some(vec!["offer.sold".to_string()]),
+                secret: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(field_errors(err), vec!["events"]);
+}
+
+#[tokio::test]
+async fn deleting_a_subscription_drops_its_deliveries() {
+    let (url, receiver) = receiver(Vec::new()).await;
+    let service = service();
+    let subscription = subscribe(&service, &url, &[]).await;
+    service
+        .fan_out(&message("offer.created"), start())
+        .await
+        .unwrap();
+
+    service.delete_subscription(&subscription.id).await.unwrap();
+
+    assert!(service.deliver_due(start()).await.unwrap().is_empty());
+    assert!(receiver.requests().is_empty());
+    assert!(matches!(
+        service.deliveries(&subscription.id, 20).await,
+        Err(WebhookError::NotFound)
+    ));
+}
+
+#[tokio::test]
+async fn a_black_holed_endpoint_does_not_hold_up_other_subscriptions() {
+    let (url, receiver) = receiver(Vec::new()).await;
+    let service = service_with_timeout(Duration::from_secs(1));
+    let stuck = subscribe(&service, &url.replace("/hooks", "/black-hole"), &[]).await;
+    let healthy = subscribe(&service, &url, &[]).await;
+    for _ in 0..3 {
+        service
+            .fan_out(&message("offer.updated"), start())
+            .await
+            .unwrap();
+    }
+
+    let started = std::time::Instant::now();
+    let attempted = service.deliver_due(start()).await.unwrap();
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    assert_eq!(receiver.requests().len(), 3);
+    let delivered = service.deliveries(&healthy.id, 20).await.unwrap();
+    assert!(
+        delivered
+            .iter()
+            .all(|delivery| delivery.status == DeliveryStatus::Delivered)
+    );
+
+    // The first timeout leaves the other deliveries for the next run.
+    let stuck_attempts: Vec<usize> = service
+        .deliveries(&stuck.id, 20)
+        .await
+        .unwrap()
+        .iter()
+        .map(|delivery| delivery.attempts.len())
+        .collect();
+    assert_eq!(stuck_attempts.iter().sum::<usize>(), 1);
+    assert_eq!(attempted.len(), 4);
+}
@@ -14,8 +14,8 @@ async fn main() -> anyhow::Result<()> {
         .with_env_filter(EnvFilter::from_default_env())
         .init();
 
-    let mongodb_uri =
-        std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".to_string());
+    let mongodb_uri = std::env::var("MONGODB_URI")
+        .unwrap_or_else(|_| "mongodb://localhost:27017/?directConnection=true".to_string());
     let mongodb_db = std::env::var("MONGODB_DB").unwrap_or_else(|_| "laposte".to_string());
 
     tracing::info!("Connecting to MongoDB...");
@@ -1,18 +1,90 @@
 use crate::application::news_service::NewsService;
 use crate::domain::model::News;
-use crate::domain::ports::news_repository::NewsRepository;
+use crate::domain::ports::news_repository::{NewsError, NewsRepository};
 use futures::StreamExt;
 use lapin::{
-    Channel, Connection, ConnectionProperties, ExchangeKind, options::*, types::FieldTable,
+    Channel, Connection, ConnectionProperties, Consumer, ExchangeKind, options::*,
+    types::FieldTable,
 };
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::future::Future;
 use std::sync::Arc;
 
+/// Status of the offers `CityStats` counts.
+const PUBLISHED: &str = "published";
+const FILLED: &str = "filled";
+
+/// Shape shared by `offer.created`, `offer.deleted`, `offer.restored`,
+/// `offer.filled` and `offer.expired`.
+#[derive(Debug, Deserialize)]
+struct OfferEvent {
+    id: String,
+    city: String,
+    domain: String,
+    /// Missing from events published before Erasmumu sent it, which were all
+    /// about published offers.
+    #[serde(default)]
+    status: Option<String>,
+}
+
+impl OfferEvent {
+    fn is_published(&self) -> bool {
+        self.status
+            .as_deref()
+            .is_none_or(|status| status == PUBLISHED)
+    }
+}
+
+/// City and domain an offer counts under in `CityStats`.
+type Counted<'a> = Option<(&'a str, &'a str)>;
+
 #[derive(Debug, Deserialize)]
-struct OfferCreatedEvent {
+struct OfferUpdatedEvent {
     id: String,
     city: String,
     domain: String,
+    #[serde(default)]
+    status: Option<String>,
+    changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FieldChange {
+    field: String,
+    from: serde_json::Value,
+}
+
+impl OfferUpdatedEvent {
+    /// Value of a text field before the update, or its current value if it did not change.
+    fn previous<'a>(&'a self, field: &str, current: &'a str) -> &'a str {
+        self.changes
+            .iter()
+            .find(|change| change.field == field)
+            .and_then(|change| change.from.as_str())
+            .unwrap_or(current)
+    }
+
+    /// City and domain the offer was counted under before and after the
+    /// update, if it was published. An offer that just got filled still counts
+    /// after it: `offer.filled` takes it out.
+    fn counted(&self) -> (Counted<'_>, Counted<'_>) {
+        let before = (
+            self.previous("city", &self.city),
+            self.previous("domain", &self.domain),
+        );
+        let after = (self.city.as_str(), self.domain.as_str());
+        let Some(status) = self.status.as_deref() else {
+            return (Some(before), Some(after));
+        };
+
+        let was_published = self.previous("status", status) == PUBLISHED;
+        let is_published = status == PUBLISHED || (status == FILLED && was_published);
+        (
+            was_published.then_some(before),
+            is_published.then_some(after),
+        )
+    }
 }
 
 async fn setup_channel(amqp_url: &str) -> Result<Channel, lapin::Error> {
@@ -34,32 +106,18 @@ async fn setup_channel(amqp_url: &str) -> Result<Channel, lapin::Error> {
     Ok(channel)
 }
 
-pub async fn start_subscribers<R: NewsRepository + 'static>(
+/// Declares a durable queue bound to `routing_key` and starts consuming it.
+async fn bind_consumer(
     amqp_url: &str,
-    service: Arc<NewsService<R>>,
-) {
-    // Subscriber for news.created
-    match setup_news_subscriber(amqp_url, service.clone()).await {
-        Ok(_) => tracing::info!("MI8 news.created subscriber started"),
-        Err(e) => tracing::error!("Failed to start news.created subscriber: {}", e),
-    }
-
-    // Subscriber for offer.created
-    match setup_offer_subscriber(amqp_url, service).await {
-        Ok(_) => tracing::info!("MI8 offer.created subscriber started"),
-        Err(e) => tracing::error!("Failed to start offer.created subscriber: {}", e),
-    }
-}
-
-async fn setup_news_subscriber<R: NewsRepository + 'static>(
-    amqp_url: &str,
-    service: Arc<NewsService<R>>,
-) -> Result<(), lapin::Error> {
+    queue: &str,
+    routing_key: &str,
+    consumer_tag: &str,
+) -> Result<Consumer, lapin::Error> {
     let channel = setup_channel(amqp_url).await?;
 
     channel
         .queue_declare(
-            "mi8.news.created",
+            queue,
             QueueDeclareOptions {
                 durable: true,
                 ..Default::default()
@@ -70,121 +128,312 @@ async fn setup_news_subscriber<R: NewsRepository + 'static>(
 
     channel
         .queue_bind(
-            "mi8.news.created",
+            queue,
             "polymove.events",
-            "news.created",
+            routing_key,
             QueueBindOptions::default(),
             FieldTable::default(),
         )
         .await?;
 
-    let mut consumer = channel
+    channel
         .basic_consume(
-            "mi8.news.created",
-            "mi8-news-consumer",
+            queue,
+            consumer_tag,
             BasicConsumeOptions::default(),
             FieldTable::default(),
         )
-        .await?;
+        .await
+}
 
+/// Deserializes every delivery as `E` and hands it to `handle`, acking regardless
+/// of the outcome so that a malformed message is never redelivered forever.
+fn spawn_consumer<E, F, Fut>(mut consumer: Consumer, routing_key: &'static str, handle: F)
+where
+    E: DeserializeOwned + Send + 'static,
+    F: Fn(E) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), NewsError>> + Send,
+{
     tokio::spawn(async move {
         while let Some(delivery_result) = consumer.next().await {
             match delivery_result {
                 Ok(delivery) => {
-                    match serde_json::from_slice::<News>(&delivery.data) {
-                        Ok(news) => {
-                            tracing::info!(
-                                "MI8 received news.created: {} in {}",
-                                news.name,
-                                news.city
-                            );
-                            if let Err(e) = service.create_news(news).await {
-                                tracing::error!("Failed to process news: {}", e);
+                    match serde_json::from_slice::<E>(&delivery.data) {
+                        Ok(event) => {
+                            if let Err(e) = handle(event).await {
+                                tracing::error!("Failed to process {}: {}", routing_key, e);
                             }
                         }
                         Err(e) => {
-                            tracing::error!("Failed to deserialize news: {}", e);
+                            tracing::error!("Failed to deserialize {} event: {}", routing_key, e);
                         }
                     }
                     let _ = delivery.ack(BasicAckOptions::default()).await;
                 }
                 Err(e) => {
-                    tracing::error!("News consumer error: {}", e);
+                    tracing::error!("{} consumer error: {}", routing_key, e);
                 }
             }
         }
     });
+}
 
-    Ok(())
+pub async fn start_subscribers<R: NewsRepository + 'static>(
+    amqp_url: &str,
+    service: Arc<NewsService<R>>,
+) {
+    // Subscriber for news.created
+    match setup_news_subscriber(amqp_url, service.clone()).await {
+        Ok(_) => tracing::info!("MI8 news.created subscriber started"),
+        Err(e) => tracing::error!("Failed to start news.created subscriber: {}", e),
+    }
+
+    // Subscribers keeping CityStats in line with the offer catalogue
+    match setup_offer_subscribers(amqp_url, service).await {
+        Ok(_) => tracing::info!("MI8 offer subscribers started"),
+        Err(e) => tracing::error!("Failed to start offer subscribers: {}", e),
+    }
 }
 
-async fn setup_offer_subscriber<R: NewsRepository + 'static>(
+async fn setup_news_subscriber<R: NewsRepository + 'static>(
     amqp_url: &str,
     service: Arc<NewsService<R>>,
 ) -> Result<(), lapin::Error> {
-    let channel = setup_channel(amqp_url).await?;
+    let consumer = bind_consumer(
+        amqp_url,
+        "mi8.news.created",
+        "news.created",
+        "mi8-news-consumer",
+    )
+    .await?;
 
-    channel
-        .queue_declare(
-            "mi8.offer.created",
-            QueueDeclareOptions {
-                durable: true,
-                ..Default::default()
-            },
-            FieldTable::default(),
-        )
-        .await?;
+    spawn_consumer(consumer, "news.created", move |news: News| {
+        let service = service.clone();
+        async move {
+            tracing::info!("MI8 received news.created: {} in {}", news.name, news.city);
+            service.create_news(news).await
+        }
+    });
 
-    channel
-        .queue_bind(
-            "mi8.offer.created",
-            "polymove.events",
-            "offer.created",
-            QueueBindOptions::default(),
-            FieldTable::default(),
-        )
-        .await?;
+    Ok(())
+}
 
-    let mut consumer = channel
-        .basic_consume(
-            "mi8.offer.created",
-            "mi8-offer-consumer",
-            BasicConsumeOptions::default(),
-            FieldTable::default(),
-        )
-        .await?;
+async fn setup_offer_subscribers<R: NewsRepository + 'static>(
+    amqp_url: &str,
+    service: Arc<NewsService<R>>,
+) -> Result<(), lapin::Error> {
+    let consumer = bind_consumer(
+        amqp_url,
+        "mi8.offer.created",
+        "offer.created",
+        "mi8-offer-consumer",
+    )
+    .await?;
+    let created_service = service.clone();
+    spawn_consumer(consumer, "offer.created", move |event: OfferEvent| {
+        let service = created_service.clone();
+        async move {
+            tracing::info!(
+                "MI8 received offer.created: {} in {} (domain: {})",
+                event.id,
+                event.city,
+                event.domain
+            );
+            if !event.is_published() {
+                return Ok(());
+            }
+            service
+                .increment_city_offer_stats(&event.city, &event.domain)
+                .await
+        }
+    });
 
-    tokio::spawn(async move {
-        while let Some(delivery_result) = consumer.next().await {
-            match delivery_result {
-                Ok(delivery) => {
-                    match serde_json::from_slice::<OfferCreatedEvent>(&delivery.data) {
-                        Ok(event) => {
-                            tracing::info!(
-                                "MI8 received offer.created: {} in {} (domain: {})",
-                                event.id,
-                                event.city,
-                                event.domain
-                            );
-                            if let Err(e) = service
-                                .increment_city_offer_stats(&event.city, &event.domain)
-                                .await
-                            {
-                                tracing::error!("Failed to update city offer stats: {}", e);
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to deserialize offer.created event: {}", e);
-                        }
-                    }
-                    let _ = delivery.ack(BasicAckOptions::default()).await;
-                }
-                Err(e) => {
-                    tracing::error!("Offer consumer error: {}", e);
-                }
+    let consumer = bind_consumer(
+        amqp_url,
+        "mi8.offer.updated",
+        "offer.updated",
+        "mi8-offer-updated-consumer",
+    )
+    .await?;
+    let updated_service = service.clone();
+    spawn_consumer(
+        consumer,
+        "offer.updated",
+        move |event: OfferUpdatedEvent| {
+            let service = updated_service.clone();
+            async move {
+                tracing::info!("MI8 received offer.updated: {}", event.id);
+                let (from, to) = event.counted();
+                service.move_city_offer_stats(from, to).await
+            }
+        },
+    );
+
+    let consumer = bind_consumer(
+        amqp_url,
+        "mi8.offer.deleted",
+        "offer.deleted",
+        "mi8-offer-deleted-consumer",
+    )
+    .await?;
+    let deleted_service = service.clone();
+    spawn_consumer(consumer, "offer.deleted", move |event: OfferEvent| {
+        let service = deleted_service.clone();
+        async move {
+            tracing::info!(
+                "MI8 received offer.deleted: {} in {} (domain: {})",
+                event.id,
+                event.city,
+                event.domain
+            );
+            if !event.is_published() {
+                return Ok(());
             }
+            service
+                .decrement_city_offer_stats(&event.city, &event.domain)
+                .await
         }
     });
 
+    let consumer = bind_consumer(
+        amqp_url,
+        "mi8.offer.restored",
+        "offer.restored",
+        "mi8-offer-restored-consumer",
+    )
+    .await?;
+    let restored_service = service.clone();
+    spawn_consumer(consumer, "offer.restored", move |event: OfferEvent| {
+        let service = restored_service.clone();
+        async move {
+            tracing::info!(
+                "MI8 received offer.restored: {} in {} (domain: {})",
+                event.id,
+                event.city,
+                event.domain
+            );
+            if !event.is_published() {
+                return Ok(());
+            }
+            service
+                .increment_city_offer_stats(&event.city, &event.domain)
+                .await
+        }
+    });
+
+    // Only published offers are filled or expired, so both always take one out.
+    for (routing_key, queue, consumer_tag) in [
+        (
+            "offer.filled",
+            "mi8.offer.filled",
+            "mi8-offer-filled-consumer",
+        ),
+        (
+            "offer.expired",
+            "mi8.offer.expired",
+            "mi8-offer-expired-consumer",
+        ),
+    ] {
+        let consumer = bind_consumer(amqp_url, queue, routing_key, consumer_tag).await?;
+        let closed_service = service.clone();
+        spawn_consumer(consumer, routing_key, move |event: OfferEvent| {
+            let service = closed_service.clone();
+            async move {
+                tracing::info!(
+                    "MI8 received {}: {} in {} (domain: {})",
+                    routing_key,
+                    event.id,
+                    event.city,
+                    event.domain
+                );
+                service
+                    .decrement_city_offer_stats(&event.city, &event.domain)
+                    .await
+            }
+        });
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(status: Option<&str>, changes: serde_json::Value) -> OfferUpdatedEvent {
+        serde_json::from_value(serde_json::json!({
+            "id": "offer-1",
+            "city": "Lyon",
+            "domain": "AI",
+            "status": status,
+            "changes": changes,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn a_move_to_another_city_is_counted_in_both() {
+        let moved = event(
+            Some(PUBLISHED),
+            serde_json::json!([{ "field": "city", "from": "Paris", "to": "Lyon" }]),
+        );
+        assert_eq!(
+            moved.counted(),
+            (Some(("Paris", "AI")), Some(("Lyon", "AI")))
+        );
+    }
+
+    #[test]
+    fn a_change_of_domain_is_counted_in_both() {
+        let moved = event(
+            Some(PUBLISHED),
+            serde_json::json!([{ "field": "domain", "from": "Finance", "to": "AI" }]),
+        );
+        assert_eq!(
+            moved.counted(),
+            (Some(("Lyon", "Finance")), Some(("Lyon", "AI")))
+        );
+    }
+
+    #[test]
+    fn availability_flips_count_on_the_published_side_only() {
+        let published = event(
+            Some(PUBLISHED),
+            serde_json::json!([{ "field": "status", "from": "draft", "to": "published" }]),
+        );
+        assert_eq!(published.counted(), (None, Some(("Lyon", "AI"))));
+
+        let withdrawn = event(
+            Some("archived"),
+            serde_json::json!([{ "field": "status", "from": "published", "to": "archived" }]),
+        );
+        assert_eq!(withdrawn.counted(), (Some(("Lyon", "AI")), None));
+
+        let filled = event(
+            Some(FILLED),
+            serde_json::json!([{ "field": "status", "from": "published", "to": "filled" }]),
+        );
+        assert_eq!(
+            filled.counted(),
+            (Some(("Lyon", "AI")), Some(("Lyon", "AI")))
+        );
+    }
+
+    #[test]
+    fn a_no_op_update_counts_the_offer_where_it_was() {
+        let unchanged = event(Some(PUBLISHED), serde_json::json!([]));
+        assert_eq!(
+            unchanged.counted(),
+            (Some(("Lyon", "AI")), Some(("Lyon", "AI")))
+        );
+
+        let draft = event(Some("draft"), serde_json::json!([]));
+        assert_eq!(draft.counted(), (None, None));
+
+        let legacy = event(None, serde_json::json!([]));
+        assert_eq!(
+            legacy.counted(),
+            (Some(("Lyon", "AI")), Some(("Lyon", "AI")))
+        );
+    }
+}
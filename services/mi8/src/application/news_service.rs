@@ -115,4 +115,35 @@ impl<R: NewsRepository> NewsService<R> {
         stats.increment(domain);
         self.repository.update_city_stats(&stats).await
     }
+
+    pub async fn decrement_city_offer_stats(
+        &self,
+        city: &str,
+        domain: &str,
+    ) -> Result<(), NewsError> {
+        let Some(mut stats) = self.repository.get_city_stats(city).await? else {
+            return Ok(());
+        };
+        stats.decrement(domain);
+        self.repository.update_city_stats(&stats).await
+    }
+
+    /// Moves an offer's contribution to the stats after an update, `None`
+    /// standing for an offer that did not count (was or is no longer published).
+    pub async fn move_city_offer_stats(
+        &self,
+        from: Option<(&str, &str)>,
+        to: Option<(&str, &str)>,
+    ) -> Result<(), NewsError> {
+        if from == to {
+            return Ok(());
+        }
+        if let Some((city, domain)) = from {
+            self.decrement_city_offer_stats(city, domain).await?;
+        }
+        if let Some((city, domain)) = to {
+            self.increment_city_offer_stats(city, domain).await?;
+        }
+        Ok(())
+    }
 }
@@ -46,6 +46,19 @@ impl CityStats {
         *self.offers_by_domain.entry(domain.to_string()).or_insert(0) += 1;
         self.last_offer_date = chrono::Utc::now().to_rfc3339();
     }
+
+    /// Ignores domains with no offer counted, so that a replayed or duplicate
+    /// event cannot bring the total below the real count.
+    pub fn decrement(&mut self, domain: &str) {
+        let Some(count) = self.offers_by_domain.get_mut(domain) else {
+            return;
+        };
+        *count -= 1;
+        if *count <= 0 {
+            self.offers_by_domain.remove(domain);
+        }
+        self.total_offers = (self.total_offers - 1).max(0);
+    }
 }
 
 impl CityScore {
@@ -65,3 +78,38 @@ impl CityScore {
         self.quality_of_life + self.safety + self.economy + self.culture
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrement_removes_emptied_domains() {
+        let mut stats = CityStats::new("Lyon".to_string());
+        stats.increment("AI");
+        stats.increment("AI");
+        stats.increment("Finance");
+
+        stats.decrement("Finance");
+        stats.decrement("AI");
+
+        assert_eq!(stats.total_offers, 1);
+        assert_eq!(
+            stats.offers_by_domain,
+            HashMap::from([("AI".to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn decrement_never_goes_below_zero() {
+        let mut stats = CityStats::new("Lyon".to_string());
+        stats.increment("AI");
+
+        stats.decrement("AI");
+        stats.decrement("AI");
+        stats.decrement("Finance");
+
+        assert_eq!(stats.total_offers, 0);
+        assert!(stats.offers_by_domain.is_empty());
+    }
+}
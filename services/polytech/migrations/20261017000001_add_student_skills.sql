@@ -0,0 +1 @@
+ALTER TABLE students ADD COLUMN IF NOT EXISTS skills TEXT[] NOT NULL DEFAULT '{}';
@@ -1,7 +1,27 @@
-use crate::ports::erasmumu_client::{ErasmumuClient, ErasmumuOffer};
-use reqwest::Client;
+use crate::ports::erasmumu_client::{
+    ErasmumuClient, ErasmumuCompany, ErasmumuOffer, ErasmumuOfferFacets, ErasmumuOfferPage,
+    ErasmumuRejection, OfferFilters, OfferPageRequest,
+};
+use reqwest::{Client, Response};
 use std::time::Duration;
 
+/// Tells requests Erasmumu rejected apart from the ones it failed to answer.
+async fn check_status(response: Response) -> Result<Response, anyhow::Error> {
+    let status = response.status();
+    if status.is_client_error() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(ErasmumuRejection {
+            status: status.as_u16(),
+            body,
+        }
+        .into());
+    }
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("Erasmumu returned status {}", status));
+    }
+    Ok(response)
+}
+
 #[derive(Clone)]
 pub struct ErasmumuReqwestClient {
     client: Client,
@@ -33,72 +53,66 @@ impl ErasmumuClient for ErasmumuReqwestClient {
         response.json::<ErasmumuOffer>().await.map_err(Into::into)
     }
 
-    async fn register_internship(&self, offer_id: &str) -> Result<bool, anyhow::Error> {
-        let url = format!("{}/offer/{}", self.base_url, offer_id);
-        let resp = self.client.get(&url).send().await?;
+    async fn fetch_company(&self, company_id: &str) -> Result<ErasmumuCompany, anyhow::Error> {
+        let url = format!("{}/company/{}", self.base_url, company_id);
+        let response = self.client.get(&url).send().await?;
 
-        if resp.status() == reqwest::StatusCode::NOT_FOUND {
-            return Ok(false);
-        }
-        if !resp.status().is_success() {
-            return Err(anyhow::anyhow!("Erasmumu returned {}", resp.status()));
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Erasmumu returned status {}",
+                response.status()
+            ));
         }
 
-        let offer: serde_json::Value = resp.json().await?;
-        let available = offer
-            .get("available")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-        if !available {
-            return Ok(false);
-        }
+        response.json::<ErasmumuCompany>().await.map_err(Into::into)
+    }
 
-        let update_resp = self
+    async fn register_internship(&self, offer_id: &str) -> Result<bool, anyhow::Error> {
+        let url = format!("{}/offer/{}/reserve", self.base_url, offer_id);
+        let resp = self
             .client
-            .put(&url)
-            .json(&serde_json::json!({ "available": false }))
+            .post(&url)
+            .header("X-Actor", "polytech")
             .send()
             .await?;
 
-        if !update_resp.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Failed to update offer availability: {}",
-                update_resp.status()
-            ));
+        match resp.status() {
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::CONFLICT => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => Err(anyhow::anyhow!("Erasmumu returned {}", status)),
         }
-
-        Ok(true)
     }
 
     async fn fetch_offers(
         &self,
-        city: Option<String>,
-        domain: Option<String>,
-    ) -> Result<Vec<ErasmumuOffer>, anyhow::Error> {
-        let mut url = format!("{}/offer", self.base_url);
-        let mut params = Vec::new();
-
-        if let Some(c) = city {
-            params.push(format!("city={}", c));
-        } else if let Some(d) = domain {
-            params.push(format!("domain={}", d));
-        }
-
-        if !params.is_empty() {
-            url.push('?');
-            url.push_str(&params.join("&"));
-        }
-
-        let response = self.client.get(&url).send().await?;
+        filters: &OfferFilters,
+        page: &OfferPageRequest,
+    ) -> Result<ErasmumuOfferPage, anyhow::Error> {
+        let url = format!("{}/offer", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .query(filters)
+            .query(page)
+            .send()
+            .await?;
+        let response = check_status(response).await?;
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Erasmumu returned status {}",
-                response.status()
-            ));
-        }
+        let page = response.json::<ErasmumuOfferPage>().await?;
+        Ok(page)
+    }
 
-        let offers = response.json::<Vec<ErasmumuOffer>>().await?;
-        Ok(offers)
+    async fn fetch_offer_facets(
+        &self,
+        filters: &OfferFilters,
+    ) -> Result<ErasmumuOfferFacets, anyhow::Error> {
+        let url = format!("{}/offers/facets", self.base_url);
+        let response = self.client.get(&url).query(filters).send().await?;
+        let response = check_status(response).await?;
+
+        response
+            .json::<ErasmumuOfferFacets>()
+            .await
+            .map_err(Into::into)
     }
 }
@@ -38,6 +38,8 @@ pub struct CreateStudentRequest {
     pub firstname: String,
     pub name: String,
     pub domain: String,
+    #[serde(default)]
+    pub skills: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -45,6 +47,7 @@ pub struct UpdateStudentRequest {
     pub firstname: Option<String>,
     pub name: Option<String>,
     pub domain: Option<String>,
+    pub skills: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
@@ -121,6 +124,7 @@ where
             get(get_latest_news_in_city::<R, E, M>),
         )
         .route("/offers", get(offers::get_offers::<R, E, M>))
+        .route("/offers/facets", get(offers::get_offer_facets::<R, E, M>))
         .route(
             "/students/{id}/recommended-offers",
             get(offers::get_recommended_offers::<R, E, M>),
@@ -158,7 +162,12 @@ where
 {
     let student = state
         .service
-        .create_student(payload.firstname, payload.name, payload.domain.clone())
+        .create_student(
+            payload.firstname,
+            payload.name,
+            payload.domain.clone(),
+            payload.skills,
+        )
         .await
         .map_err(Response::from)?;
 
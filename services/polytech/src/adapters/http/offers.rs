@@ -1,13 +1,16 @@
 use crate::adapters::http::AppState;
 use crate::application::offer_aggregation_service::EnrichedOffer;
 use crate::domain::student::Student;
-use crate::ports::erasmumu_client::ErasmumuClient;
+use crate::ports::erasmumu_client::{
+    ErasmumuClient, ErasmumuOfferFacets, ErasmumuRejection, OfferFilters, OfferPageRequest,
+    OfferSort, SkillMatch, SortOrder,
+};
 use crate::ports::mi8_client::Mi8Client;
 use crate::ports::student_repository::StudentRepository;
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
@@ -17,12 +20,56 @@ use uuid::Uuid;
 #[derive(Deserialize)]
 pub struct OffersQuery {
     #[serde(default = "default_limit")]
-    pub limit: usize,
+    pub limit: u64,
+    #[serde(default)]
+    pub offset: u64,
+    pub sort: Option<OfferSort>,
+    pub order: Option<SortOrder>,
+    pub q: Option<String>,
     pub city: Option<String>,
     pub domain: Option<String>,
+    pub company_id: Option<String>,
+    pub min_salary: Option<f64>,
+    pub max_salary: Option<f64>,
+    pub start_from: Option<String>,
+    pub end_until: Option<String>,
+    pub near: Option<String>,
+    pub radius_km: Option<f64>,
+    pub skills: Option<String>,
+    pub skill_match: Option<SkillMatch>,
+}
+
+impl From<&OffersQuery> for OfferPageRequest {
+    fn from(query: &OffersQuery) -> Self {
+        Self {
+            offset: query.offset,
+            limit: query.limit,
+            sort: query.sort,
+            order: query.order,
+        }
+    }
+}
+
+impl From<&OffersQuery> for OfferFilters {
+    fn from(query: &OffersQuery) -> Self {
+        Self {
+            q: query.q.clone(),
+            city: query.city.clone(),
+            domain: query.domain.clone(),
+            company_id: query.company_id.clone(),
+            min_salary: query.min_salary,
+            max_salary: query.max_salary,
+            start_from: query.start_from.clone(),
+            end_until: query.end_until.clone(),
+            near: query.near.clone(),
+            radius_km: query.radius_km,
+            skills: query.skills.clone(),
+            skill_match: query.skill_match,
+        }
+    }
 }
 
-fn default_limit() -> usize {
+fn default_limit() -> u64 {
     10
 }
 
@@ -40,6 +87,10 @@ fn default_recommended_limit() -> usize {
 #[derive(Serialize)]
 pub struct OffersResponse {
     pub offers: Vec<EnrichedOffer>,
+    pub total: u64,
+    pub offset: u64,
+    pub limit: u64,
+    pub next_offset: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -48,6 +99,23 @@ pub struct RecommendedOffersResponse {
     pub offers: Vec<EnrichedOffer>,
 }
 
+/// Hands back what Erasmumu rejected, such as bad filters, and reports
+/// anything else as Erasmumu being unavailable.
+fn erasmumu_failure(e: anyhow::Error, context: &str, message: &'static str) -> Response {
+    if let Some(rejection) = e.downcast_ref::<ErasmumuRejection>() {
+        tracing::debug!("{}: {}", context, rejection);
+        let status = StatusCode::from_u16(rejection.status).unwrap_or(StatusCode::BAD_REQUEST);
+        return (
+            status,
+            [(header::CONTENT_TYPE, "application/json")],
+            rejection.body.clone(),
+        )
+            .into_response();
+    }
+    tracing::error!("{}: {}", context, e);
+    (StatusCode::SERVICE_UNAVAILABLE, message).into_response()
+}
+
 pub async fn get_offers<R, E, M>(
     State(state): State<Arc<AppState<R, E, M>>>,
     Query(params): Query<OffersQuery>,
@@ -59,18 +127,48 @@ where
 {
     match state
         .offer_aggregation_service
-        .get_enriched_offers(params.city, params.domain, params.limit)
+        .get_enriched_offers(OfferFilters::from(&params), OfferPageRequest::from(&params))
         .await
     {
-        Ok(offers) => Ok(Json(OffersResponse { offers })),
-        Err(e) => {
-            tracing::error!("Failed to fetch aggregated offers: {}", e);
-            Err((
-                StatusCode::SERVICE_UNAVAILABLE,
-                "Failed to aggregate offers. Dependent service might be unavailable.",
-            )
-                .into_response())
+        Ok((offers, total)) => {
+            let next = params.offset + offers.len() as u64;
+            Ok(Json(OffersResponse {
+                offers,
+                total,
+                offset: params.offset,
+                limit: params.limit,
+                next_offset: (next < total).then_some(next),
+            }))
         }
+        Err(e) => Err(erasmumu_failure(
+            e,
+            "Failed to fetch aggregated offers",
+            "Failed to aggregate offers. Dependent service might be unavailable.",
+        )),
+    }
+}
+
+/// Counts behind the Explorer filters, for the same filters as `GET /offers`.
+pub async fn get_offer_facets<R, E, M>(
+    State(state): State<Arc<AppState<R, E, M>>>,
+    Query(params): Query<OffersQuery>,
+) -> Result<Json<ErasmumuOfferFacets>, Response>
+where
+    R: StudentRepository + Send + Sync + 'static,
+    E: ErasmumuClient + Send + Sync + 'static,
+    M: Mi8Client + Send + Sync + 'static,
+{
+    match state
+        .erasmumu_client
+        .fetch_offer_facets(&OfferFilters::from(&params))
+        .await
+    {
+        Ok(facets) => Ok(Json(facets)),
+        Err(e) => Err(erasmumu_failure(
+            e,
+            "Failed to fetch offer facets",
+            "Failed to fetch offer facets. Erasmumu might be unavailable.",
+        )),
     }
 }
 
@@ -21,15 +21,16 @@ impl StudentRepository for PostgresStudentRepository {
     async fn create(&self, student: &Student) -> Result<Student, StudentError> {
         sqlx::query_as::<_, Student>(
             r#"
-            INSERT INTO students (id, firstname, name, domain)
-            VALUES ($1, $2, $3, $4)
-            RETURNING id, firstname, name, domain
+            INSERT INTO students (id, firstname, name, domain, skills)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, firstname, name, domain, skills
             "#,
         )
         .bind(student.id)
         .bind(&student.firstname)
         .bind(&student.name)
         .bind(&student.domain)
+        .bind(&student.skills)
         .fetch_one(&self.pool)
         .await
         .map_err(|e: sqlx::Error| StudentError::DatabaseError(e.to_string()))
@@ -38,7 +39,7 @@ impl StudentRepository for PostgresStudentRepository {
     async fn get(&self, id: Uuid) -> Result<Student, StudentError> {
         sqlx::query_as::<_, Student>(
             r#"
-            SELECT id, firstname, name, domain
+            SELECT id, firstname, name, domain, skills
             FROM students
             WHERE id = $1
             "#,
@@ -53,7 +54,7 @@ impl StudentRepository for PostgresStudentRepository {
     async fn list_by_domain(&self, domain: &str) -> Result<Vec<Student>, StudentError> {
         sqlx::query_as::<_, Student>(
             r#"
-            SELECT id, firstname, name, domain
+            SELECT id, firstname, name, domain, skills
             FROM students
             WHERE domain = $1
             "#,
@@ -68,14 +69,15 @@ impl StudentRepository for PostgresStudentRepository {
         sqlx::query_as::<_, Student>(
             r#"
             UPDATE students
-            SET firstname = $1, name = $2, domain = $3
-            WHERE id = $4
-            RETURNING id, firstname, name, domain
+            SET firstname = $1, name = $2, domain = $3, skills = $4
+            WHERE id = $5
+            RETURNING id, firstname, name, domain, skills
             "#,
         )
         .bind(&student_data.firstname)
         .bind(&student_data.name)
         .bind(&student_data.domain)
+        .bind(&student_data.skills)
         .bind(id)
         .fetch_optional(&self.pool)
         .await
@@ -1,4 +1,7 @@
-use crate::ports::erasmumu_client::{ErasmumuClient, ErasmumuOffer};
+use crate::mi8_proto::CityScore;
+use crate::ports::erasmumu_client::{
+    ErasmumuClient, ErasmumuOffer, OfferFilters, OfferPageRequest,
+};
 use crate::ports::mi8_client::Mi8Client;
 use crate::ports::student_repository::StudentRepository;
 use futures::future::join_all;
@@ -7,6 +10,9 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Largest page Erasmumu serves.
+const ERASMUMU_PAGE_SIZE: u64 = 100;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct EnrichedOffer {
     pub id: String,
@@ -14,15 +20,64 @@ pub struct EnrichedOffer {
     pub link: String,
     pub city: String,
     pub domain: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub skills: Vec<String>,
     pub salary: f64,
+    pub currency: String,
+    pub salary_period: String,
+    pub salary_monthly_eur: f64,
     #[serde(rename = "startDate")]
     pub start_date: String,
     #[serde(rename = "endDate")]
     pub end_date: String,
+    pub positions: u32,
+    pub remaining: u32,
+    pub company: Option<CompanySummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+    /// How well the offer fits a student's skills, only set on recommendations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_match: Option<SkillOverlap>,
     pub scores: EnrichedScores,
     pub latest_news: Vec<EnrichedNews>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CompanySummary {
+    pub id: String,
+    pub name: String,
+    pub website: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SkillOverlap {
+    /// Share of the offer's skills the student has, from 0 to 1. Offers that
+    /// list no skills score 0.
+    pub score: f64,
+    pub matched: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+impl SkillOverlap {
+    pub fn between(student_skills: &[String], offer_skills: &[String]) -> Self {
+        let (matched, missing): (Vec<String>, Vec<String>) = offer_skills
+            .iter()
+            .cloned()
+            .partition(|skill| student_skills.contains(&skill.to_lowercase()));
+        let score = if offer_skills.is_empty() {
+            0.0
+        } else {
+            matched.len() as f64 / offer_skills.len() as f64
+        };
+        Self {
+            score,
+            matched,
+            missing,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct EnrichedScores {
     pub quality_of_life: i32,
@@ -39,6 +94,17 @@ pub struct EnrichedNews {
     pub tags: Vec<String>,
 }
 
+impl From<CityScore> for EnrichedScores {
+    fn from(score: CityScore) -> Self {
+        Self {
+            quality_of_life: score.quality_of_life,
+            economy: score.economy,
+            culture: score.culture,
+            safety: score.safety,
+        }
+    }
+}
+
 impl EnrichedScores {
     pub fn default_scores() -> Self {
         Self {
@@ -72,15 +138,65 @@ where
 
     pub async fn get_enriched_offers(
         &self,
-        city: Option<String>,
-        domain: Option<String>,
-        limit: usize,
-    ) -> Result<Vec<EnrichedOffer>, anyhow::Error> {
-        let offers = self.erasmumu_client.fetch_offers(city, domain).await?;
+        filters: OfferFilters,
+        page: OfferPageRequest,
+    ) -> Result<(Vec<EnrichedOffer>, u64), anyhow::Error> {
+        let offer_page = self.erasmumu_client.fetch_offers(&filters, &page).await?;
+        Ok((self.enrich(offer_page.offers).await, offer_page.total))
+    }
 
-        let limited_offers: Vec<ErasmumuOffer> = offers.into_iter().take(limit).collect();
+    /// Every offer matching `filters`, fetched a page at a time. Pages come
+    /// newest first, so an offer created during the read only pushes the
+    /// others down, and those seen again on the next page are dropped.
+    async fn fetch_all_offers(
+        &self,
+        filters: &OfferFilters,
+    ) -> Result<Vec<ErasmumuOffer>, anyhow::Error> {
+        let mut offers = Vec::new();
+        let mut seen = HashSet::new();
+        let mut offset = 0;
+        loop {
+            let page = OfferPageRequest {
+                offset,
+                limit: ERASMUMU_PAGE_SIZE,
+                sort: None,
+                order: None,
+            };
+            let offer_page = self.erasmumu_client.fetch_offers(filters, &page).await?;
+            let fetched = offer_page.offers.len() as u64;
+            offset += fetched;
+            offers.extend(
+                offer_page
+                    .offers
+                    .into_iter()
+                    .filter(|offer| seen.insert(offer.id.clone())),
+            );
+            if fetched < ERASMUMU_PAGE_SIZE || offset >= offer_page.total {
+                return Ok(offers);
+            }
+        }
+    }
 
-        let cities: HashSet<String> = limited_offers.iter().map(|o| o.city.clone()).collect();
+    /// Scores of the cities the offers are in, looking each city up once.
+    async fn city_scores(&self, offers: &[ErasmumuOffer]) -> HashMap<String, EnrichedScores> {
+        let cities: HashSet<&str> = offers.iter().map(|o| o.city.as_str()).collect();
+        let scores = cities.into_iter().map(|city| async move {
+            let scores = match self.mi8_client.get_city_score(city.to_string()).await {
+                Ok(s) => EnrichedScores::from(s),
+                Err(e) => {
+                    tracing::warn!("Failed to fetch city score for {}: {}", city, e);
+                    EnrichedScores::default_scores()
+                }
+            };
+            (city.to_string(), scores)
+        });
+        join_all(scores).await.into_iter().collect()
+    }
+
+    /// Adds city scores and news, and company details, looking each city and
+    /// company up once.
+    async fn enrich(&self, offers: Vec<ErasmumuOffer>) -> Vec<EnrichedOffer> {
+        let cities: HashSet<String> = offers.iter().map(|o| o.city.clone()).collect();
 
         let mut mi8_futures = Vec::new();
         for unique_city in cities {
@@ -96,18 +212,41 @@ where
             mi8_futures.push(fut);
         }
 
-        let results = join_all(mi8_futures).await;
+        let company_ids: HashSet<String> =
+            offers.iter().filter_map(|o| o.company_id.clone()).collect();
+        let company_futures = company_ids.into_iter().map(|company_id| {
+            let erasmumu = self.erasmumu_client.clone();
+            async move {
+                let result = erasmumu.fetch_company(&company_id).await;
+                (company_id, result)
+            }
+        });
+
+        let (results, company_results) =
+            futures::join!(join_all(mi8_futures), join_all(company_futures));
+
+        let mut company_cache: HashMap<String, CompanySummary> = HashMap::new();
+        for (company_id, result) in company_results {
+            match result {
+                Ok(company) => {
+                    company_cache.insert(
+                        company_id,
+                        CompanySummary {
+                            id: company.id,
+                            name: company.name,
+                            website: company.website,
+                        },
+                    );
+                }
+                Err(e) => tracing::warn!("Failed to fetch company {}: {}", company_id, e),
+            }
+        }
 
         let mut city_cache: HashMap<String, (EnrichedScores, Vec<EnrichedNews>)> = HashMap::new();
 
         for (unique_city, score_res, news_res) in results {
             let scores = match score_res {
-                Ok(s) => EnrichedScores {
-                    quality_of_life: s.quality_of_life,
-                    economy: s.economy,
-                    culture: s.culture,
-                    safety: s.safety,
-                },
+                Ok(s) => EnrichedScores::from(s),
                 Err(e) => {
                     tracing::warn!("Failed to fetch city score for {}: {}", unique_city, e);
                     EnrichedScores::default_scores()
@@ -134,11 +273,15 @@ where
         }
 
         let mut enriched_offers = Vec::new();
-        for offer in limited_offers {
+        for offer in offers {
             let (scores, news) = city_cache
                 .get(&offer.city)
                 .cloned()
                 .unwrap_or_else(|| (EnrichedScores::default_scores(), vec![]));
+            let company = offer
+                .company_id
+                .as_ref()
+                .and_then(|company_id| company_cache.get(company_id).cloned());
 
             enriched_offers.push(EnrichedOffer {
                 id: offer.id,
@@ -146,15 +289,25 @@ where
                 link: offer.link,
                 city: offer.city,
                 domain: offer.domain,
+                description: offer.description,
+                skills: offer.skills,
                 salary: offer.salary,
+                salary_monthly_eur: offer.salary_monthly_eur.unwrap_or(offer.salary),
+                currency: offer.currency,
+                salary_period: offer.salary_period,
                 start_date: offer.start_date,
                 end_date: offer.end_date,
+                positions: offer.positions,
+                remaining: offer.remaining,
+                company,
+                distance_km: offer.distance_km,
+                skill_match: None,
                 scores,
                 latest_news: news,
             });
         }
 
-        Ok(enriched_offers)
+        enriched_offers
     }
 
     pub async fn get_recommended_offers(
@@ -169,21 +322,54 @@ where
             .await
             .map_err(|e| anyhow::anyhow!("Student not found: {}", e))?;
 
-        let mut enriched_offers = self
-            .get_enriched_offers(None, Some(student.domain.clone()), 100)
+        let offers = self
+            .fetch_all_offers(&OfferFilters {
+                domain: Some(student.domain.clone()),
+                ..Default::default()
+            })
             .await?;
 
-        if let Some(sort) = sort_by {
-            enriched_offers.sort_by(|a, b| match sort.as_str() {
-                "safety" => b.scores.safety.cmp(&a.scores.safety),
-                "economy" => b.scores.economy.cmp(&a.scores.economy),
-                "quality_of_life" => b.scores.quality_of_life.cmp(&a.scores.quality_of_life),
-                "culture" => b.scores.culture.cmp(&a.scores.culture),
+        // Rank on what the offers say themselves, and on city scores when
+        // asked to, so that only the offers returned get enriched.
+        let sort = sort_by.unwrap_or_else(|| "skills".to_string());
+        let city_score: Option<fn(&EnrichedScores) -> i32> = match sort.as_str() {
+            "safety" => Some(|s| s.safety),
+            "economy" => Some(|s| s.economy),
+            "quality_of_life" => Some(|s| s.quality_of_life),
+            "culture" => Some(|s| s.culture),
+            _ => None,
+        };
+        let city_scores = match city_score {
+            Some(_) => self.city_scores(&offers).await,
+            None => HashMap::new(),
+        };
+        let monthly_salary =
+            |offer: &ErasmumuOffer| offer.salary_monthly_eur.unwrap_or(offer.salary);
+
+        let mut ranked: Vec<(ErasmumuOffer, SkillOverlap)> = offers
+            .into_iter()
+            .map(|offer| {
+                let skill_match = SkillOverlap::between(&student.skills, &offer.skills);
+                (offer, skill_match)
+            })
+            .collect();
+        // Best skill fit first unless another ranking is asked for.
+        ranked.sort_by(
+            |(a, a_match), (b, b_match)| match (sort.as_str(), city_score) {
+                (_, Some(score)) => score(&city_scores[&b.city]).cmp(&score(&city_scores[&a.city])),
+                ("skills", _) => b_match.score.total_cmp(&a_match.score),
+                ("salary", _) => monthly_salary(b).total_cmp(&monthly_salary(a)),
                 _ => std::cmp::Ordering::Equal,
-            });
-        }
+            },
+        );
+        ranked.truncate(limit);
 
-        let limited_offers = enriched_offers.into_iter().take(limit).collect();
-        Ok((student, limited_offers))
+        let (offers, skill_matches): (Vec<ErasmumuOffer>, Vec<SkillOverlap>) =
+            ranked.into_iter().unzip();
+        let mut recommended = self.enrich(offers).await;
+        for (offer, skill_match) in recommended.iter_mut().zip(skill_matches) {
+            offer.skill_match = Some(skill_match);
+        }
+        Ok((student, recommended))
     }
 }
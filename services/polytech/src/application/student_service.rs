@@ -1,4 +1,4 @@
-use crate::domain::student::Student;
+use crate::domain::student::{Student, normalize_skills};
 use crate::ports::student_repository::{StudentError, StudentRepository};
 use uuid::Uuid;
 
@@ -16,8 +16,9 @@ impl<R: StudentRepository> StudentService<R> {
         firstname: String,
         name: String,
         domain: String,
+        skills: Vec<String>,
     ) -> Result<Student, StudentError> {
-        let student = Student::new(firstname, name, domain);
+        let student = Student::new(firstname, name, domain, skills);
         self.repository.create(&student).await
     }
 
@@ -38,6 +39,7 @@ impl<R: StudentRepository> StudentService<R> {
         firstname: Option<String>,
         name: Option<String>,
         domain: Option<String>,
+        skills: Option<Vec<String>>,
     ) -> Result<Student, StudentError> {
         let mut current_student = self.repository.get(id).await?;
 
@@ -50,6 +52,9 @@ impl<R: StudentRepository> StudentService<R> {
         if let Some(d) = domain {
             current_student.domain = d;
         }
+        if let Some(s) = skills {
+            current_student.skills = normalize_skills(s);
+        }
 
         self.repository.update(id, current_student).await
     }
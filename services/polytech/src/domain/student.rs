@@ -7,15 +7,30 @@ pub struct Student {
     pub firstname: String,
     pub name: String,
     pub domain: String,
+    /// Lowercased, like the skills Erasmumu stores on offers.
+    pub skills: Vec<String>,
 }
 
 impl Student {
-    pub fn new(firstname: String, name: String, domain: String) -> Self {
+    pub fn new(firstname: String, name: String, domain: String, skills: Vec<String>) -> Self {
         Self {
             id: Uuid::new_v4(),
             firstname,
             name,
             domain,
+            skills: normalize_skills(skills),
         }
     }
 }
+
+/// Trims, lowercases and de-duplicates skills, dropping empty ones.
+pub fn normalize_skills(skills: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(skills.len());
+    for skill in skills {
+        let skill = skill.trim().to_lowercase();
+        if !skill.is_empty() && !normalized.contains(&skill) {
+            normalized.push(skill);
+        }
+    }
+    normalized
+}
@@ -7,23 +7,173 @@ pub struct ErasmumuOffer {
     pub link: String,
     pub city: String,
     pub domain: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
     pub salary: f64,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    #[serde(default = "default_salary_period")]
+    pub salary_period: String,
+    /// Falls back to `salary` for Erasmumu versions that do not normalize it.
+    #[serde(default)]
+    pub salary_monthly_eur: Option<f64>,
     pub start_date: String,
     pub end_date: String,
+    pub positions: u32,
+    pub remaining: u32,
+    #[serde(default)]
+    pub company_id: Option<String>,
+    #[serde(default)]
+    pub distance_km: Option<f64>,
+}
+
+fn default_currency() -> String {
+    "EUR".to_string()
+}
+
+fn default_salary_period() -> String {
+    "monthly".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasmumuCompany {
+    pub id: String,
+    pub name: String,
+    pub website: Option<String>,
+    pub description: Option<String>,
+    pub cities: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OfferFilters {
+    /// Words looked up in titles and descriptions; results then come most
+    /// relevant first unless a sort is asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_id: Option<String>,
+    /// Salary bounds in euros per month, whatever the currency and period offers are paid in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_salary: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_salary: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_until: Option<String>,
+    /// `lat,lon`, together with `radius_km`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub near: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius_km: Option<f64>,
+    /// Comma-separated skills, matched according to `skill_match`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_match: Option<SkillMatch>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillMatch {
+    Any,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferSort {
+    Salary,
+    StartDate,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OfferPageRequest {
+    pub offset: u64,
+    pub limit: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<OfferSort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErasmumuOfferPage {
+    pub offers: Vec<ErasmumuOffer>,
+    pub total: u64,
+}
+
+/// Counts of offers matching a filter per city, domain, salary bucket (in euros
+/// per month) and start month (`YYYY-MM`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasmumuOfferFacets {
+    pub total: u64,
+    pub cities: Vec<FacetCount>,
+    pub domains: Vec<FacetCount>,
+    pub salaries: Vec<SalaryBucket>,
+    pub start_months: Vec<FacetCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalaryBucket {
+    pub min: f64,
+    /// Missing on the last, open-ended bucket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    pub count: u64,
+}
+
+/// Erasmumu turned a request down with a 4xx, e.g. for contradictory filters.
+/// Carries its answer so that it can be handed back to the caller as is.
+#[derive(Debug, thiserror::Error)]
+#[error("Erasmumu rejected the request with status {status}")]
+pub struct ErasmumuRejection {
+    pub status: u16,
+    pub body: String,
 }
 
 pub trait ErasmumuClient: Send + Sync {
     fn fetch_offers(
         &self,
-        city: Option<String>,
-        domain: Option<String>,
-    ) -> impl std::future::Future<Output = Result<Vec<ErasmumuOffer>, anyhow::Error>> + Send;
+        filters: &OfferFilters,
+        page: &OfferPageRequest,
+    ) -> impl std::future::Future<Output = Result<ErasmumuOfferPage, anyhow::Error>> + Send;
+
+    fn fetch_offer_facets(
+        &self,
+        filters: &OfferFilters,
+    ) -> impl std::future::Future<Output = Result<ErasmumuOfferFacets, anyhow::Error>> + Send;
 
     fn fetch_offer(
         &self,
         offer_id: &str,
     ) -> impl std::future::Future<Output = Result<ErasmumuOffer, anyhow::Error>> + Send;
 
+    fn fetch_company(
+        &self,
+        company_id: &str,
+    ) -> impl std::future::Future<Output = Result<ErasmumuCompany, anyhow::Error>> + Send;
+
     fn register_internship(
         &self,
         offer_id: &str,
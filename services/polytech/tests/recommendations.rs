@@ -0,0 +1,214 @@
+use polytech::application::offer_aggregation_service::OfferAggregationService;
+use polytech::domain::student::Student;
+use polytech::mi8_proto::{CityScore, News};
+use polytech::ports::erasmumu_client::{
+    ErasmumuClient, ErasmumuCompany, ErasmumuOffer, ErasmumuOfferFacets, ErasmumuOfferPage,
+    OfferFilters, OfferPageRequest,
+};
+use polytech::ports::mi8_client::Mi8Client;
+use polytech::ports::student_repository::{StudentError, StudentRepository};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Knows a single student.
+struct FakeStudents(Student);
+
+impl StudentRepository for FakeStudents {
+    async fn create(&self, student: &Student) -> Result<Student, StudentError> {
+        Ok(student.clone())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Student, StudentError> {
+        if id == self.0.id {
+            Ok(self.0.clone())
+        } else {
+            Err(StudentError::NotFound)
+        }
+    }
+
+    async fn list_by_domain(&self, _domain: &str) -> Result<Vec<Student>, StudentError> {
+        Ok(vec![self.0.clone()])
+    }
+
+    async fn update(&self, _id: Uuid, student: Student) -> Result<Student, StudentError> {
+        Ok(student)
+    }
+
+    async fn delete(&self, _id: Uuid) -> Result<(), StudentError> {
+        Ok(())
+    }
+}
+
+/// Serves offers newest first, in pages of at most 100 like Erasmumu. Each
+/// page served lets one of the `arriving` offers in, ahead of the others.
+#[derive(Default)]
+struct FakeErasmumu {
+    offers: Mutex<Vec<ErasmumuOffer>>,
+    arriving: Mutex<Vec<ErasmumuOffer>>,
+    company_lookups: AtomicUsize,
+}
+
+impl ErasmumuClient for FakeErasmumu {
+    async fn fetch_offers(
+        &self,
+        filters: &OfferFilters,
+        page: &OfferPageRequest,
+    ) -> Result<ErasmumuOfferPage, anyhow::Error> {
+        anyhow::ensure!(page.limit <= 100, "limit must be at most 100");
+        let mut offers = self.offers.lock().unwrap();
+        let matching: Vec<ErasmumuOffer> = offers
+            .iter()
+            .filter(|offer| filters.domain.as_ref().is_none_or(|d| *d == offer.domain))
+            .cloned()
+            .collect();
+        if let Some(arrived) = self.arriving.lock().unwrap().pop() {
+            offers.insert(0, arrived);
+        }
+        Ok(ErasmumuOfferPage {
+            total: matching.len() as u64,
+            offers: matching
+                .into_iter()
+                .skip(page.offset as usize)
+                .take(page.limit as usize)
+                .collect(),
+        })
+    }
+
+    async fn fetch_offer_facets(
+        &self,
+        _filters: &OfferFilters,
+    ) -> Result<ErasmumuOfferFacets, anyhow::Error> {
+        anyhow::bail!("no facets")
+    }
+
+    async fn fetch_offer(&self, offer_id: &str) -> Result<ErasmumuOffer, anyhow::Error> {
+        self.offers
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|offer| offer.id == offer_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no offer {}", offer_id))
+    }
+
+    async fn fetch_company(&self, company_id: &str) -> Result<ErasmumuCompany, anyhow::Error> {
+        self.company_lookups.fetch_add(1, Ordering::SeqCst);
+        anyhow::bail!("no company {}", company_id)
+    }
+
+    async fn register_internship(&self, _offer_id: &str) -> Result<bool, anyhow::Error> {
+        Ok(true)
+    }
+}
+
+/// Has nothing to say about any city.
+struct SilentMi8;
+
+impl Mi8Client for SilentMi8 {
+    async fn get_latest_news(&self, _limit: i32) -> Result<Vec<News>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+
+    async fn get_city_score(&self, city: String) -> Result<CityScore, anyhow::Error> {
+        anyhow::bail!("no score for {}", city)
+    }
+
+    async fn get_latest_news_in_city(
+        &self,
+        _city: String,
+        _limit: i32,
+    ) -> Result<Vec<News>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+}
+
+fn offer(index: usize, skills: &[&str]) -> ErasmumuOffer {
+    ErasmumuOffer {
+        id: format!("offer-{index}"),
+        title: format!("Intern {index}"),
+        link: format!("https://example.com/offers/{index}"),
+        city: "Lyon".to_string(),
+        domain: "AI".to_string(),
+        description: None,
+        skills: skills.iter().map(|skill| skill.to_string()).collect(),
+        salary: 1200.0,
+        currency: "EUR".to_string(),
+        salary_period: "monthly".to_string(),
+        salary_monthly_eur: Some(1200.0),
+        start_date: "2026-06-01".to_string(),
+        end_date: "2026-09-30".to_string(),
+        positions: 1,
+        remaining: 1,
+        company_id: Some(format!("company-{index}")),
+        distance_km: None,
+    }
+}
+
+fn student(skills: &[&str]) -> Student {
+    Student::new(
+        "Ada".to_string(),
+        "Lovelace".to_string(),
+        "AI".to_string(),
+        skills.iter().map(|skill| skill.to_string()).collect(),
+    )
+}
+
+#[tokio::test]
+async fn recommendations_rank_offers_past_the_first_page() {
+    let student = student(&["rust", "python"]);
+    // The best fit is the oldest offer, far past the first page.
+    let mut offers: Vec<ErasmumuOffer> = (0..250).map(|index| offer(index, &["java"])).collect();
+    offers.push(offer(250, &["rust", "python"]));
+    offers.push(ErasmumuOffer {
+        domain: "Finance".to_string(),
+        ..offer(251, &["rust"])
+    });
+    let erasmumu = Arc::new(FakeErasmumu {
+        offers: Mutex::new(offers),
+        ..Default::default()
+    });
+    let service = OfferAggregationService::new(
+        Arc::new(FakeStudents(student.clone())),
+        erasmumu.clone(),
+        Arc::new(SilentMi8),
+    );
+
+    let (_, recommended) = service
+        .get_recommended_offers(student.id, 2, None)
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = recommended.iter().map(|offer| offer.id.as_str()).collect();
+    assert_eq!(ids[0], "offer-250");
+    assert_eq!(recommended[0].skill_match.as_ref().unwrap().score, 1.0);
+    assert_eq!(recommended[1].skill_match.as_ref().unwrap().score, 0.0);
+    assert_eq!(recommended.len(), 2);
+    // Only the offers returned are enriched.
+    assert_eq!(erasmumu.company_lookups.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn recommendations_list_each_offer_once_while_offers_arrive() {
+    let student = student(&["rust"]);
+    let erasmumu = Arc::new(FakeErasmumu {
+        offers: Mutex::new((0..150).map(|index| offer(index, &["rust"])).collect()),
+        arriving: Mutex::new(vec![offer(150, &["rust"])]),
+        ..Default::default()
+    });
+    let service = OfferAggregationService::new(
+        Arc::new(FakeStudents(student.clone())),
+        erasmumu,
+        Arc::new(SilentMi8),
+    );
+
+    let (_, recommended) = service
+        .get_recommended_offers(student.id, 200, None)
+        .await
+        .unwrap();
+
+    let ids: HashSet<&str> = recommended.iter().map(|offer| offer.id.as_str()).collect();
+    assert_eq!(recommended.len(), 150);
+    assert_eq!(ids.len(), 150);
+}
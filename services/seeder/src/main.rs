@@ -12,17 +12,35 @@ struct CreateOfferRequest {
     link: String,
     city: String,
     domain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
     salary: f64,
     start_date: String,
     end_date: String,
 }
 
 #[derive(Debug, Deserialize)]
-struct OfferResponse {
+struct ImportReport {
+    received: usize,
+    accepted: Vec<ImportedRow>,
+    errors: Vec<ImportRowError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportedRow {
+    row: usize,
     id: String,
-    title: String,
-    city: String,
-    domain: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportRowError {
+    row: usize,
+    error: String,
+    #[serde(default)]
+    fields: Vec<serde_json::Value>,
+    /// Set when the offer already exists, e.g. from an earlier run.
+    #[serde(default)]
+    duplicate_of: Option<String>,
 }
 
 async fn wait_for_health(client: &Client, service_name: &str, url: &str) -> Result<()> {
@@ -49,30 +67,25 @@ async fn wait_for_health(client: &Client, service_name: &str, url: &str) -> Resu
     anyhow::bail!("Timed out waiting for {service_name}");
 }
 
-async fn create_offer(
+async fn import_offers(
     client: &Client,
     erasmumu_base_url: &str,
-    payload: CreateOfferRequest,
-) -> Result<OfferResponse> {
+    offers: &[CreateOfferRequest],
+) -> Result<ImportReport> {
     let response = client
-        .post(format!("{erasmumu_base_url}/offer"))
-        .json(&payload)
+        .post(format!("{erasmumu_base_url}/offers/import"))
+        .json(offers)
         .send()
         .await
-        .with_context(|| format!("Failed to create offer {}", payload.title))?;
+        .context("Failed to import offers")?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
-        anyhow::bail!(
-            "Offer creation failed for {}: {} {}",
-            payload.title,
-            status,
-            body
-        );
+        anyhow::bail!("Offer import failed: {} {}", status, body);
     }
 
-    response.json().await.context("Invalid offer response")
+    response.json().await.context("Invalid import report")
 }
 
 fn demo_offers() -> Result<Vec<CreateOfferRequest>> {
@@ -101,11 +114,40 @@ async fn main() -> Result<()> {
     let offers = demo_offers()?;
 
     info!("Seeding demo offers...");
-    for offer in offers {
-        let created = create_offer(&client, &erasmumu_base_url, offer).await?;
+    let report = import_offers(&client, &erasmumu_base_url, &offers).await?;
+    for imported in &report.accepted {
+        let Some(offer) = imported
+            .row
+            .checked_sub(1)
+            .and_then(|index| offers.get(index))
+        else {
+            continue;
+        };
         info!(
             "Created offer: {} [{} - {}] -> {}",
-            created.title, created.domain, created.city, created.id
+            offer.title, offer.domain, offer.city, imported.id
+        );
+    }
+    let mut rejected_count = 0;
+    for rejected in &report.errors {
+        if let Some(existing_id) = &rejected.duplicate_of {
+            info!(
+                "Offer on row {} already exists -> {}",
+                rejected.row, existing_id
+            );
+            continue;
+        }
+        rejected_count += 1;
+        warn!(
+            "Rejected offer on row {}: {} {:?}",
+            rejected.row, rejected.error, rejected.fields
+        );
+    }
+    if rejected_count > 0 {
+        anyhow::bail!(
+            "{} of {} offers were rejected",
+            rejected_count,
+            report.received
         );
     }
 